@@ -1,5 +1,7 @@
-def solve():
-    with open("problems/problem1") as f:
+import sys
+
+def solve(path="problems/problem1"):
+    with open(path) as f:
         lines = f.readlines()
 
     nrs = [int(line) for line in lines]
@@ -20,4 +22,4 @@ def solve():
     print("Problem 2:", prob2)
         
 if __name__ == '__main__':
-    solve()
\ No newline at end of file
+    solve(*sys.argv[1:2])
\ No newline at end of file
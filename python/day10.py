@@ -1,5 +1,7 @@
-def solve():
-    with open("problems/problem10") as f:
+import sys
+
+def solve(path="problems/problem10"):
+    with open(path) as f:
         lines = f.readlines()
 
     prob1 = 0
@@ -59,4 +61,4 @@ def solve():
     print("Problem 2:", autocomplete_scores[len(autocomplete_scores) // 2])
 
 if __name__ == '__main__':
-    solve()
\ No newline at end of file
+    solve(*sys.argv[1:2])
\ No newline at end of file
@@ -1,3 +1,5 @@
+import sys
+
 def adjacent_locations(x, y, width, height):
     for dx in range(-1, 2):
         for dy in range(-1, 2):
@@ -9,8 +11,8 @@ def adjacent_locations(x, y, width, height):
             if nx >= 0 and ny >= 0 and nx < width and ny < height:
                 yield (nx, ny)
 
-def solve():
-    with open("problems/problem11") as f:
+def solve(path="problems/problem11"):
+    with open(path) as f:
         lines = f.readlines()
 
     grid = [[int(x) for x in line.strip()] for line in lines if line.strip()]
@@ -61,4 +63,4 @@ def solve():
 
 
 if __name__ == '__main__':
-    solve()
\ No newline at end of file
+    solve(*sys.argv[1:2])
\ No newline at end of file
@@ -1,5 +1,7 @@
-def solve():
-    with open("problems/problem12") as f:
+import sys
+
+def solve(path="problems/problem12"):
+    with open(path) as f:
         lines = f.readlines()
 
     adjacent = {}
@@ -57,4 +59,4 @@ def solve():
         
         
 if __name__ == '__main__':
-    solve()
\ No newline at end of file
+    solve(*sys.argv[1:2])
\ No newline at end of file
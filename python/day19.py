@@ -1,3 +1,5 @@
+import sys
+
 # S3 = {(), (0, 1, 2), (0, 2, 1), (0, 1), (1, 2), (0, 2)}
 # Of those: (), (0, 1, 2), (0, 2, 1) are even and (0, 1), (1, 2), (2, 3) are odd
 S3_WITH_SIGNS = [(1, (0, 1, 2)), (1, (1, 2, 0)), (1, (2, 0, 1)), (-1, (1, 0, 2)), (-1, (0, 2, 1)), (-1, (2, 1, 0))]
@@ -93,8 +95,8 @@ def find_rotation_and_offset(set1: BeaconSet, set2: BeaconSet):
                 if m >= 12:
                     return rot, (d1, d2, d3)
 
-def solve():
-    with open("problems/problem19") as f:
+def solve(path="problems/problem19"):
+    with open(path) as f:
         lines = f.readlines()
         lines.append('\n')
 
@@ -162,4 +164,4 @@ def solve():
     print("Problem 2:", max_distance)
 
 if __name__ == '__main__':
-    solve()
+    solve(*sys.argv[1:2])
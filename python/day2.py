@@ -1,5 +1,7 @@
-def solve():
-    with open("problems/problem2") as f:
+import sys
+
+def solve(path="problems/problem2"):
+    with open(path) as f:
         lines = f.readlines()
 
     pos1 = 0
@@ -31,4 +33,4 @@ def solve():
     print("Problem 2:", pos2 * depth2)
 
 if __name__ == '__main__':
-    solve()
\ No newline at end of file
+    solve(*sys.argv[1:2])
\ No newline at end of file
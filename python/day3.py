@@ -1,4 +1,5 @@
 import time
+import sys
 
 def most_common_bit(bits):
     res = 0
@@ -18,8 +19,8 @@ def bits_to_number(bits):
 
     return res
 
-def solve():
-    with open("problems/problem3") as f:
+def solve(path="problems/problem3"):
+    with open(path) as f:
         lines = f.readlines()
 
     nrs = [[int(digit) for digit in line.strip()] for line in lines]
@@ -66,4 +67,4 @@ def solve2(xs):
     print("Problem 2:", oxygen_generator_rating * co2_scrubber_rating)
 
 if __name__ == '__main__':
-    solve()
\ No newline at end of file
+    solve(*sys.argv[1:2])
\ No newline at end of file
@@ -1,3 +1,5 @@
+import sys
+
 # this could just be chosen as len(moves) but a "big" number is more readable
 INF = 2**32
 
@@ -31,8 +33,8 @@ def determine_winning_move_count(board, moves, moves_dict, size=5):
     else:
         return None
 
-def solve():
-    with open("problems/problem4") as f:
+def solve(path="problems/problem4"):
+    with open(path) as f:
         lines = f.readlines()
         lines.append("")
 
@@ -72,4 +74,4 @@ def solve():
     print("Problem 2:", final_score_2)
 
 if __name__ == '__main__':
-    solve()
\ No newline at end of file
+    solve(*sys.argv[1:2])
\ No newline at end of file
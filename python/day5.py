@@ -1,3 +1,5 @@
+import sys
+
 def sign(x):
     if x > 0:
         return 1
@@ -6,8 +8,8 @@ def sign(x):
     else:
         return 0
 
-def solve():
-    with open("problems/problem5") as f:
+def solve(path="problems/problem5"):
+    with open(path) as f:
         lines = f.readlines()
         
     hit_count_1 = {}
@@ -49,4 +51,4 @@ def solve():
     print("Problem 2:", prob2)
 
 if __name__ == '__main__':
-    solve()
\ No newline at end of file
+    solve(*sys.argv[1:2])
\ No newline at end of file
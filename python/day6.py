@@ -1,5 +1,7 @@
-def solve():
-    with open("problems/problem6") as f:
+import sys
+
+def solve(path="problems/problem6"):
+    with open(path) as f:
         line = f.readline()
 
     # This is dynamic programming but recursive because I dont like writing dp programs
@@ -26,4 +28,4 @@ def solve():
     print("Problem 2:", sum(f(x, 256) for x in fish))
 
 if __name__ == '__main__':
-    solve()
\ No newline at end of file
+    solve(*sys.argv[1:2])
\ No newline at end of file
@@ -1,8 +1,10 @@
+import sys
+
 def solve():
     pass
 
-def solve():
-    with open("problems/problem7") as f:
+def solve(path="problems/problem7"):
+    with open(path) as f:
         line = f.readline()
 
     crabs = [int(x) for x in line.split(",")]
@@ -33,4 +35,4 @@ def minimize(crabs, max_, cost):
     return best
 
 if __name__ == '__main__':
-    solve()
\ No newline at end of file
+    solve(*sys.argv[1:2])
\ No newline at end of file
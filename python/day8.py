@@ -1,3 +1,5 @@
+import sys
+
 CHARS = ['a', 'b', 'c', 'd', 'e', 'f', 'g']
 
 def parse_pattern(w):
@@ -10,12 +12,12 @@ def permute(pi, bin_pattern):
         res[pi[i]] = b
     return tuple(res)
 
-def solve():
+def solve(path="problems/problem8"):
     DIGITS = [
         'abcefg', 'cf', 'acdeg', 'acdfg', 'bcdf', 'abdfg', 'abdefg', 'acf', 'abcdefg', 'abcdfg'
     ]
 
-    with open("problems/problem8") as f:
+    with open(path) as f:
         lines = f.readlines()
 
     observations = [
@@ -97,4 +99,4 @@ def solve():
     print("Problem 2:", prob2)
 
 if __name__ == '__main__':
-    solve()
\ No newline at end of file
+    solve(*sys.argv[1:2])
\ No newline at end of file
@@ -1,3 +1,5 @@
+import sys
+
 DIRECTIONS = [(-1, 0), (1, 0), (0, -1), (0, 1)]
 
 def adjacent_locations(x, y, width, height):
@@ -7,8 +9,8 @@ def adjacent_locations(x, y, width, height):
         if nx >= 0 and ny >= 0 and nx < width and ny < height:
             yield (nx, ny)
 
-def solve():
-    with open("problems/problem9") as f:
+def solve(path="problems/problem9"):
+    with open(path) as f:
         lines = f.readlines()
 
     grid = [[int(x) for x in line.strip()] for line in lines if not len(line.strip()) == 0]
@@ -57,4 +59,4 @@ def solve():
     print("Problem 2:", prob2)
 
 if __name__ == '__main__':
-    solve()
\ No newline at end of file
+    solve(*sys.argv[1:2])
\ No newline at end of file
@@ -0,0 +1,131 @@
+//! Counting of heap allocations. With the `count-allocations` feature, the runner installs
+//! [`Counting`] as its global allocator and [`measure`] reports how often and how much a
+//! closure allocated. The counters are kept per thread, so days that are solved concurrently
+//! do not mix up their numbers.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Whether allocations are counted, i.e. whether the crate was built with the
+/// `count-allocations` feature. The counts are only meaningful in a binary that installs
+/// [`Counting`] as its global allocator, like the runner does; elsewhere they stay at zero.
+pub const ENABLED: bool = cfg!(feature = "count-allocations");
+
+/// What a piece of code allocated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocations {
+    /// Number of allocations, reallocations included
+    pub count: u64,
+    /// Bytes requested by all allocations together
+    pub bytes: u64,
+    /// The most bytes that were live at any point, on top of the ones that were live before
+    pub peak: u64,
+}
+
+#[derive(Clone, Copy)]
+struct Counters {
+    count: u64,
+    bytes: u64,
+    // Signed, since memory can be freed by another thread than the one that allocated it
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters { count: 0, bytes: 0, live: 0, peak: 0 })
+    };
+}
+
+fn update(f: impl FnOnce(&mut Counters)) {
+    // The counters are gone while a thread shuts down, its last allocations are not counted
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        f(&mut c);
+        counters.set(c);
+    });
+}
+
+fn record_alloc(size: usize) {
+    update(|c| {
+        c.count += 1;
+        c.bytes += size as u64;
+        c.live += size as i64;
+        c.peak = c.peak.max(c.live);
+    });
+}
+
+fn record_free(size: usize) {
+    update(|c| c.live -= size as i64);
+}
+
+/// The system allocator, counting what the current thread allocates
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record_alloc(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record_alloc(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record_free(layout.size());
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record_free(layout.size());
+        record_alloc(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Run `f` and count what it allocates on the current thread. None if allocations are not
+/// counted. Measurements can be nested.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    if !ENABLED {
+        return (f(), None);
+    }
+
+    let before = COUNTERS.with(Cell::get);
+
+    // Track the peak of `f` alone, the outer one is restored afterwards
+    update(|c| c.peak = c.live);
+    let result = f();
+    let after = COUNTERS.with(Cell::get);
+    update(|c| c.peak = c.peak.max(before.peak));
+
+    let allocations = Allocations {
+        count: after.count - before.count,
+        bytes: after.bytes - before.bytes,
+        peak: (after.peak - before.live).max(0) as u64,
+    };
+
+    (result, Some(allocations))
+}
+
+/// Format a number of bytes with a binary unit that fits its magnitude
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.2} {}", value, UNITS[unit])
+}
@@ -0,0 +1,189 @@
+use std::{collections::HashMap, fmt::Write, path::Path, time::Duration};
+
+use crate::solvers::{Parts, Phase, Solver};
+
+/// Summary statistics over the samples of one phase
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let secs = sorted.iter().map(|d| d.as_secs_f64()).collect::<Vec<_>>();
+        let mean = secs.iter().sum::<f64>() / n as f64;
+        let variance = secs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+
+        Some(Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// The measured phases of one day
+#[derive(Debug, Clone)]
+pub struct DayBench {
+    pub day: u8,
+    pub phases: Vec<(Phase, Stats)>,
+}
+
+/// Run a day `warmup` times without measuring and then `iterations` times, timing every
+/// phase separately. The input is parsed again in every iteration.
+pub fn bench_day(
+    solver: &Solver,
+    input: &str,
+    parts: Parts,
+    warmup: usize,
+    iterations: usize,
+) -> crate::Result<DayBench> {
+    let mut samples: HashMap<Phase, Vec<Duration>> = HashMap::new();
+
+    for i in 0..warmup + iterations {
+        let (_, timings) = solver.solve_timed(input, parts)?;
+
+        if i < warmup {
+            continue;
+        }
+
+        for phase in Phase::ALL {
+            if let Some(took) = timings.get(phase) {
+                samples.entry(phase).or_default().push(took);
+            }
+        }
+    }
+
+    let phases = Phase::ALL
+        .iter()
+        .filter_map(|phase| {
+            let stats = Stats::from_samples(samples.get(phase)?)?;
+            Some((*phase, stats))
+        })
+        .collect();
+
+    Ok(DayBench {
+        day: solver.day,
+        phases,
+    })
+}
+
+/// Previously saved results, keyed by day and phase
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    stats: HashMap<(u8, Phase), Stats>,
+}
+
+impl Baseline {
+    /// Each line of a baseline file is `<day> <phase> <min> <median> <mean> <stddev>`
+    /// with all durations given in nanoseconds.
+    pub fn load(path: &Path) -> crate::Result<Baseline> {
+        let mut stats = HashMap::new();
+
+        for line in std::fs::read_to_string(path)?.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut split = line.split_ascii_whitespace();
+
+            let day = split
+                .next()
+                .ok_or(crate::Error::InvalidInput)?
+                .parse::<u8>()?;
+            let phase = split
+                .next()
+                .and_then(Phase::from_name)
+                .ok_or(crate::Error::InvalidInput)?;
+
+            let mut next_duration = || -> crate::Result<Duration> {
+                let nanos = split
+                    .next()
+                    .ok_or(crate::Error::InvalidInput)?
+                    .parse::<u64>()?;
+                Ok(Duration::from_nanos(nanos))
+            };
+
+            let s = Stats {
+                min: next_duration()?,
+                median: next_duration()?,
+                mean: next_duration()?,
+                stddev: next_duration()?,
+            };
+
+            stats.insert((day, phase), s);
+        }
+
+        Ok(Baseline { stats })
+    }
+
+    pub fn save(path: &Path, results: &[DayBench]) -> crate::Result<()> {
+        let mut out = String::from("# day phase min median mean stddev (all in ns)\n");
+
+        for result in results {
+            for (phase, s) in &result.phases {
+                let _ = writeln!(
+                    out,
+                    "{} {} {} {} {} {}",
+                    result.day,
+                    phase.name(),
+                    s.min.as_nanos(),
+                    s.median.as_nanos(),
+                    s.mean.as_nanos(),
+                    s.stddev.as_nanos()
+                );
+            }
+        }
+
+        Ok(std::fs::write(path, out)?)
+    }
+
+    pub fn get(&self, day: u8, phase: Phase) -> Option<&Stats> {
+        self.stats.get(&(day, phase))
+    }
+}
+
+/// Relative change of the median compared to the baseline, e.g. 0.1 for 10% slower
+pub fn relative_change(current: &Stats, baseline: &Stats) -> f64 {
+    let base = baseline.median.as_secs_f64();
+
+    if base == 0.0 {
+        return 0.0;
+    }
+
+    (current.median.as_secs_f64() - base) / base
+}
+
+/// Format a duration with a unit that fits its magnitude
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+
+    if nanos < 1_000 {
+        format!("{} ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2} µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2} ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2} s", nanos as f64 / 1e9)
+    }
+}
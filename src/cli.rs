@@ -0,0 +1,301 @@
+use std::path::PathBuf;
+
+use aoc2021::{
+    input::InputSource,
+    solvers::{Part, Parts},
+};
+
+pub const USAGE: &str = "\
+Usage: aoc2021 [COMMAND] [OPTIONS] [DAYS...]
+
+Commands:
+  run               Solve the given days and print the answers (default)
+  verify            Solve the given days and compare the answers against the
+                    expected answers
+  bench             Time the given days over several iterations and report
+                    statistics, optionally comparing them against a baseline
+  parity            Solve the given days and also run the Python reference
+                    solutions on the same inputs, comparing the answers and
+                    the time both took (Python including its start-up)
+  generate          Write random inputs for the given days, and the answers
+                    known from generating them, to a directory
+
+Days can be given as single days (5), ranges (3-7) or comma separated
+lists of both (1,3,5-7). If no days are given, all days are run.
+
+When built with the count-allocations feature, run also reports the number
+of allocations, the bytes allocated and the peak of live bytes of every part.
+
+Options:
+  -p, --part <1|2>  Only solve the given part
+  -i, --input <PATH>
+                    Read the input from a file, from a directory containing
+                    problem1, ..., problem25 or from stdin if PATH is \"-\".
+                    Defaults to the inputs in problems/
+  -a, --answers <PATH>
+                    The expected answers for verify. Defaults to the answers
+                    file next to the inputs
+  -w, --warmup <N>  Unmeasured runs per day before benchmarking (default 1)
+  -n, --iterations <N>
+                    Measured runs per day when benchmarking (default 10)
+  -V, --variant <NAME>
+                    Solve the days with the named variant instead of their
+                    default solution (see --list)
+  --variants        Benchmark all variants of the given days side by side
+  -s, --save <PATH> Save the benchmark results as a baseline
+  -b, --baseline <PATH>
+                    Compare the benchmark results against a saved baseline
+  -t, --threshold <PERCENT>
+                    How much slower than the baseline the median of a phase
+                    may be before it is flagged as a regression (default 10)
+  --python <DIR>    Where parity finds the scripts dayN.py (default python/)
+  --seed <N>        Seed of the random inputs of generate (default 2021)
+  -o, --output <DIR>
+                    Where generate writes the inputs (default generated/)
+  -f, --format <text|json|csv>
+                    Output format of run (default text)
+  -j, --jobs <N>    Solve up to N days of run concurrently (default 1)
+  -e, --explain     Also show how the answers of run came about, e.g. the
+                    shortest paths of days 15 and 23 (text format only)
+  -l, --list        List the available days and their variants and exit
+  -h, --help        Print this help and exit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Run,
+    Verify,
+    Bench,
+    Parity,
+    Generate,
+    List,
+    Help,
+}
+
+/// How run prints its results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    pub days: Vec<u8>,
+    pub parts: Parts,
+    pub format: Format,
+    /// Number of days that run solves concurrently
+    pub jobs: usize,
+    /// Whether run shows how the answers came about
+    pub explain: bool,
+    pub input: InputSource,
+    /// The variant to solve the days with, None for their default solutions
+    pub variant: Option<String>,
+    /// Whether bench compares all variants of the days
+    pub variants: bool,
+    pub answers: Option<PathBuf>,
+    pub warmup: usize,
+    pub iterations: usize,
+    pub save: Option<PathBuf>,
+    pub baseline: Option<PathBuf>,
+    /// Allowed slowdown in percent before a phase counts as a regression
+    pub threshold: f64,
+    /// The directory of the Python reference solutions
+    pub python: PathBuf,
+    pub seed: u64,
+    pub output: PathBuf,
+}
+
+impl Args {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Args, String> {
+        let mut command = Command::Run;
+        let mut days = Vec::new();
+        let mut parts = Parts::Both;
+        let mut format = Format::Text;
+        let mut jobs = 1;
+        let mut explain = false;
+        let mut input = InputSource::Bundled;
+        let mut variant = None;
+        let mut variants = false;
+        let mut answers = None;
+        let mut warmup = 1;
+        let mut iterations = 10;
+        let mut save = None;
+        let mut baseline = None;
+        let mut threshold = 10.0;
+        let mut python = PathBuf::from("python");
+        let mut seed = 2021;
+        let mut output = PathBuf::from("generated");
+
+        let mut args = args.into_iter().peekable();
+
+        match args.peek().map(|s| s.as_str()) {
+            Some("run") => {
+                args.next();
+            }
+            Some("verify") => {
+                command = Command::Verify;
+                args.next();
+            }
+            Some("bench") => {
+                command = Command::Bench;
+                args.next();
+            }
+            Some("parity") => {
+                command = Command::Parity;
+                args.next();
+            }
+            Some("generate") => {
+                command = Command::Generate;
+                args.next();
+            }
+            _ => {}
+        }
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => command = Command::Help,
+                "-l" | "--list" => command = Command::List,
+                "-e" | "--explain" => explain = true,
+                "-p" | "--part" => parts = parse_part(&value(&mut args, &arg)?)?,
+                "-f" | "--format" => format = parse_format(&value(&mut args, &arg)?)?,
+                "-j" | "--jobs" => {
+                    jobs = parse_count(&value(&mut args, &arg)?)?;
+
+                    if jobs == 0 {
+                        return Err("at least one job is required".into());
+                    }
+                }
+                "-i" | "--input" => input = InputSource::from_arg(&value(&mut args, &arg)?),
+                "-V" | "--variant" => variant = Some(value(&mut args, &arg)?),
+                "--variants" => variants = true,
+                "-a" | "--answers" => answers = Some(PathBuf::from(value(&mut args, &arg)?)),
+                "-w" | "--warmup" => warmup = parse_count(&value(&mut args, &arg)?)?,
+                "-n" | "--iterations" => {
+                    iterations = parse_count(&value(&mut args, &arg)?)?;
+
+                    if iterations == 0 {
+                        return Err("at least one iteration is required".into());
+                    }
+                }
+                "-s" | "--save" => save = Some(PathBuf::from(value(&mut args, &arg)?)),
+                "-b" | "--baseline" => baseline = Some(PathBuf::from(value(&mut args, &arg)?)),
+                "-t" | "--threshold" => {
+                    let value = value(&mut args, &arg)?;
+
+                    threshold = match value.parse::<f64>() {
+                        Ok(t) if t >= 0.0 => t,
+                        _ => return Err(format!("invalid threshold {}", value)),
+                    };
+                }
+                "--python" => python = PathBuf::from(value(&mut args, &arg)?),
+                "--seed" => {
+                    let value = value(&mut args, &arg)?;
+
+                    seed = value
+                        .parse::<u64>()
+                        .map_err(|_| format!("invalid seed {}", value))?;
+                }
+                "-o" | "--output" => output = PathBuf::from(value(&mut args, &arg)?),
+                _ if arg.starts_with('-') => return Err(format!("unknown option {}", arg)),
+                _ => days.extend(parse_days(&arg)?),
+            }
+        }
+
+        if days.is_empty() {
+            days.extend(1..=25);
+        }
+
+        // Run every day at most once and in order, no matter how they were given
+        days.sort_unstable();
+        days.dedup();
+
+        if !input.serves_all_days() && days.len() != 1 {
+            return Err("reading the input from a file or stdin requires exactly one day".into());
+        }
+
+        if variants && (variant.is_some() || save.is_some() || baseline.is_some()) {
+            return Err(
+                "--variants cannot be combined with --variant, --save or --baseline".into(),
+            );
+        }
+
+        Ok(Args {
+            command,
+            days,
+            parts,
+            format,
+            jobs,
+            explain,
+            input,
+            variant,
+            variants,
+            answers,
+            warmup,
+            iterations,
+            save,
+            baseline,
+            threshold,
+            python,
+            seed,
+            output,
+        })
+    }
+}
+
+/// The value that has to follow an option
+fn value(args: &mut impl Iterator<Item = String>, arg: &str) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("missing value for {}", arg))
+}
+
+fn parse_part(s: &str) -> Result<Parts, String> {
+    match s {
+        "1" => Ok(Parts::Only(Part::One)),
+        "2" => Ok(Parts::Only(Part::Two)),
+        _ => Err(format!("invalid part {}, expected 1 or 2", s)),
+    }
+}
+
+fn parse_format(s: &str) -> Result<Format, String> {
+    match s {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "csv" => Ok(Format::Csv),
+        _ => Err(format!("invalid format {}, expected text, json or csv", s)),
+    }
+}
+
+fn parse_count(s: &str) -> Result<usize, String> {
+    s.parse::<usize>()
+        .map_err(|_| format!("invalid count {}, expected a non-negative number", s))
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    match s.trim().parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("invalid day {}, expected a number from 1 to 25", s)),
+    }
+}
+
+/// Parse a comma separated list of days and day ranges like "1,3,5-7"
+fn parse_days(s: &str) -> Result<Vec<u8>, String> {
+    let mut ret = Vec::new();
+
+    for item in s.split(',') {
+        if let Some((start, end)) = item.split_once('-') {
+            let (start, end) = (parse_day(start)?, parse_day(end)?);
+
+            if start > end {
+                return Err(format!("invalid range {}", item));
+            }
+
+            ret.extend(start..=end);
+        } else {
+            ret.push(parse_day(item)?);
+        }
+    }
+
+    Ok(ret)
+}
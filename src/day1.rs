@@ -1,50 +1,89 @@
-const INPUT: &str = include_str!("../problems/problem1");
+use crate::{
+    error::parse_number,
+    solution::{Answer, Solution},
+    solvers::Variant,
+};
+
+pub const VARIANTS: &[Variant] = &[Variant {
+    name: "without-allocating",
+    solution: &WithoutAllocating,
+}];
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        input
+            .lines()
+            .map(|x| parse_number::<u64>(input, x))
+            .collect()
+    }
 
-pub fn solve() -> Result<(), crate::Error> {
-    let nrs = INPUT
-        .lines()
-        .map(|x| x.parse::<u64>().unwrap())
-        .collect::<Vec<_>>();
+    fn part1(nrs: &Self::Input) -> crate::Result<Answer> {
+        let mut prob1: u64 = 0;
 
-    // Part 1
-    let mut prob1 = 0;
+        for i in 1..nrs.len() {
+            if nrs[i] > nrs[i - 1] {
+                prob1 += 1;
+            }
+        }
 
-    for i in 1..nrs.len() {
-        if nrs[i] > nrs[i - 1] {
-            prob1 += 1;
+        Ok(prob1.into())
+    }
+
+    fn part2(nrs: &Self::Input) -> crate::Result<Answer> {
+        let mut prob2: u64 = 0;
+
+        for i in 3..nrs.len() {
+            // In u128, so that the sums of three depths cannot overflow
+            let [a, b, c, d] = [nrs[i - 3], nrs[i - 2], nrs[i - 1], nrs[i]].map(u128::from);
+            if a + b + c < b + c + d {
+                prob2 += 1;
+            }
         }
+
+        Ok(prob2.into())
     }
+}
 
-    println!("Problem 1: {}", prob1);
+/// Solves both parts in a single pass over the input with [`solve_without_allocating`]. All
+/// the work happens while parsing, the parts only hand out its answers.
+pub struct WithoutAllocating;
 
-    // Part 2
-    let mut prob2 = 0;
+impl Solution for WithoutAllocating {
+    type Input = (u64, u64);
 
-    for i in 3..nrs.len() {
-        let (a, b, c, d) = (nrs[i - 3], nrs[i - 2], nrs[i - 1], nrs[i]);
-        if a + b + c < b + c + d {
-            prob2 += 1;
-        }
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        solve_without_allocating(input)
     }
 
-    println!("Problem 2: {}", prob2);
+    fn part1(&(prob1, _): &Self::Input) -> crate::Result<Answer> {
+        Ok(prob1.into())
+    }
 
-    Ok(())
+    fn part2(&(_, prob2): &Self::Input) -> crate::Result<Answer> {
+        Ok(prob2.into())
+    }
 }
 
-pub fn solve_without_allocating() -> Result<(), crate::Error> {
-    let nrs = INPUT.lines().map(|x| x.parse::<u64>().unwrap());
+pub fn solve_without_allocating(input: &str) -> crate::Result<(u64, u64)> {
+    let nrs = input.lines().map(|x| parse_number::<u64>(input, x));
 
     let mut prob1 = 0;
     let mut prev = None;
 
     let mut prob2 = 0;
 
+    // The last three depths, oldest first
     let mut a = None;
     let mut b = None;
-    let mut prev_sum = None;
+    let mut c = None;
 
     for next in nrs {
+        let next = next?;
+
         // Part 1
         if let Some(prev) = prev {
             if next > prev {
@@ -54,27 +93,17 @@ pub fn solve_without_allocating() -> Result<(), crate::Error> {
 
         prev = Some(next);
 
-        // Part 2
-        let c = next;
-        let mut sum = None;
-        if let (Some(a), Some(b)) = (a, b) {
-            sum = Some(a + b + c);
-        }
-
-        if let (Some(prev_sum), Some(sum)) = (prev_sum, sum) {
-            if prev_sum < sum {
+        // Part 2, comparing the windows a + b + c and b + c + next by their only difference
+        if let Some(a) = a {
+            if a < next {
                 prob2 += 1;
             }
         }
 
         a = b;
-        b = Some(c);
-
-        prev_sum = sum;
+        b = c;
+        c = Some(next);
     }
 
-    println!("Problem 1: {}", prob1);
-    println!("Problem 1: {}", prob2);
-
-    Ok(())
+    Ok((prob1, prob2))
 }
@@ -1,69 +1,107 @@
-const INPUT: &str = include_str!("../problems/problem10");
-
-pub fn solve() -> crate::Result<()> {
-    let lines = INPUT.lines();
-
-    let mut prob1 = 0;
-    let mut autocomplete_scores = Vec::new();
+use crate::solution::{Answer, Solution};
 
+/// Check a line for syntax errors. Returns the first illegal character for corrupted lines
+/// and the still open brackets for incomplete lines.
+fn check_line(line: &str) -> Result<Vec<char>, char> {
     let mut opened: Vec<char> = Vec::new();
 
-    for line in lines {
-        opened.clear();
-
-        let mut first_illegal_character = None;
-
-        for c in line.chars() {
-            if matches!(c, '(' | '[' | '{' | '<') {
-                opened.push(c);
-            } else if let Some(x) = opened.pop() {
-                let expected = match x {
-                    '(' => ')',
-                    '[' => ']',
-                    '{' => '}',
-                    '<' => '>',
-                    _ => unreachable!()
-                };
+    for c in line.chars() {
+        if matches!(c, '(' | '[' | '{' | '<') {
+            opened.push(c);
+        } else if let Some(x) = opened.pop() {
+            let expected = match x {
+                '(' => ')',
+                '[' => ']',
+                '{' => '}',
+                '<' => '>',
+                _ => unreachable!()
+            };
 
-                if c != expected {
-                    first_illegal_character = Some(c);
-                    break;
-                }
+            if c != expected {
+                return Err(c);
             }
         }
+    }
 
-        if let Some(x) = first_illegal_character {
-            // Corrupted line
-            prob1 += match x {
-                ')' => 3,
-                ']' => 57,
-                '}' => 1197,
-                '>' => 25137,
-                _ => 0,
-            };
-        } else {
-            // Incomplete line
-            let mut score: u64 = 0;
-
-            for &c in opened.iter().rev() {
-                score *= 5;
-                score += match c {
-                    '(' => 1,
-                    '[' => 2,
-                    '{' => 3,
-                    '<' => 4,
-                    _ => unreachable!()
+    Ok(opened)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        input
+            .lines()
+            .map(|line| {
+                let illegal = line.char_indices().find(|&(_, c)| !"()[]{}<>".contains(c));
+
+                match illegal {
+                    Some((i, c)) => Err(crate::Error::parse(
+                        input,
+                        &line[i..i + c.len_utf8()],
+                        "a bracket",
+                    )),
+                    None => Ok(line.to_owned()),
+                }
+            })
+            .collect()
+    }
+
+    fn part1(lines: &Self::Input) -> crate::Result<Answer> {
+        let mut prob1: u64 = 0;
+
+        for line in lines {
+            if let Err(x) = check_line(line) {
+                // Corrupted line
+                prob1 += match x {
+                    ')' => 3,
+                    ']' => 57,
+                    '}' => 1197,
+                    '>' => 25137,
+                    _ => 0,
                 };
             }
-
-            autocomplete_scores.push(score);
         }
+
+        Ok(prob1.into())
     }
 
-    println!("Problem 1: {}", prob1);
+    fn part2(lines: &Self::Input) -> crate::Result<Answer> {
+        let mut autocomplete_scores = Vec::new();
 
-    autocomplete_scores.sort();
-    println!("Problem 2: {}", autocomplete_scores[autocomplete_scores.len() / 2]);
+        for line in lines {
+            if let Ok(opened) = check_line(line) {
+                // Incomplete line
+                let mut score: u64 = 0;
+
+                for &c in opened.iter().rev() {
+                    let points = match c {
+                        '(' => 1,
+                        '[' => 2,
+                        '{' => 3,
+                        '<' => 4,
+                        _ => unreachable!()
+                    };
+
+                    // Lines with more than 27 open brackets do not fit
+                    score = score
+                        .checked_mul(5)
+                        .and_then(|score| score.checked_add(points))
+                        .ok_or(crate::Error::InvalidInput)?;
+                }
 
-    Ok(())
-}
\ No newline at end of file
+                autocomplete_scores.push(score);
+            }
+        }
+
+        autocomplete_scores.sort();
+
+        let middle = autocomplete_scores
+            .get(autocomplete_scores.len() / 2)
+            .ok_or(crate::Error::NoSolution)?;
+
+        Ok((*middle).into())
+    }
+}
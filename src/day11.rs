@@ -1,99 +1,74 @@
-const INPUT: &str = include_str!("../problems/problem11");
+use crate::{
+    solution::{Answer, Solution},
+    util::{Grid, Neighborhood},
+};
 
-fn checked_add(a: usize, b: isize) -> Option<usize> {
-    if b >= 0 {
-        a.checked_add(b as usize)
+fn increase(pos: (usize, usize), grid: &mut Grid<u8>) -> u64 {
+    grid[pos] += 1;
+
+    // Only flash once
+    if grid[pos] == 10 {
+        let mut ret = 1;
+
+        for npos in grid.neighbors(pos, Neighborhood::Eight) {
+            ret += increase(npos, grid);
+        }
+
+        ret
     } else {
-        a.checked_sub(-b as usize)
+        0
     }
 }
 
-pub fn solve() -> crate::Result<()> {
-    let lines = INPUT.lines();
-
-    let mut grid = lines
-        .map(|line| {
-            line.chars()
-                .map(|x| -> Option<u8> { Some(x.to_digit(10)? as u8) })
-                .collect::<Option<Vec<_>>>()
-        })
-        .collect::<Option<Vec<_>>>()
-        .ok_or(crate::Error::InvalidInput)?;
-
-    let width = grid.first().ok_or(crate::Error::NoInput)?.len();
-
-    let height = grid.len();
-
-    pub fn increase(
-        x: usize,
-        y: usize,
-        grid: &mut Vec<Vec<u8>>,
-        width: usize,
-        height: usize,
-    ) -> u64 {
-        grid[y][x] += 1;
-
-        // Only flash once
-        if grid[y][x] == 10 {
-            let mut ret = 1;
-
-            for dx in -1..=1 {
-                for dy in -1..=1 {
-                    if dx == 0 && dy == 0 {
-                        continue;
-                    }
-
-                    if let (Some(nx), Some(ny)) = (checked_add(x, dx), checked_add(y, dy)) {
-                        if nx >= width || ny >= height {
-                            continue;
-                        }
-
-                        ret += increase(nx, ny, grid, width, height);
-                    }
-                }
-            }
+/// Simulate one step and return the number of octopuses that flashed
+fn step(grid: &mut Grid<u8>) -> u64 {
+    let mut flashed_this_step = 0;
 
-            ret
-        } else {
-            0
+    for pos in grid.positions() {
+        flashed_this_step += increase(pos, grid);
+    }
+
+    for v in grid.iter_mut() {
+        if *v > 9 {
+            *v = 0;
         }
     }
 
-    let mut prob1 = 0;
-    let mut prob2 = None;
+    flashed_this_step
+}
 
-    for step in 0.. {
-        let mut flashed_this_step = 0;
+pub struct Day11;
 
-        for y in 0..height {
-            for x in 0..width {
-                flashed_this_step += increase(x, y, &mut grid, width, height);
-            }
-        }
+impl Solution for Day11 {
+    type Input = Grid<u8>;
 
-        for y in 0..height {
-            for x in 0..width {
-                if grid[y][x] > 9 {
-                    grid[y][x] = 0;
-                }
-            }
-        }
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        Grid::parse(input, "a digit", |c| c.to_digit(10).map(|d| d as u8))
+    }
 
-        if step < 100 {
-            prob1 += flashed_this_step;
-        }
+    fn part1(grid: &Self::Input) -> crate::Result<Answer> {
+        let mut grid = grid.clone();
+        let mut prob1 = 0;
 
-        if prob2 == None && flashed_this_step == (width * height) as u64 {
-            prob2 = Some(step + 1);
+        for _ in 0..100 {
+            prob1 += step(&mut grid);
         }
 
-        if step >= 100 && prob2.is_some() {
-            break;
-        }
+        Ok(prob1.into())
     }
 
-    println!("Problem 1: {}", prob1);
-    println!("Problem 2: {}", prob2.unwrap());
+    fn part2(grid: &Self::Input) -> crate::Result<Answer> {
+        let mut grid = grid.clone();
 
-    Ok(())
+        // Not every grid ever synchronizes, so give up at some point instead of looping forever
+        const MAX_STEPS: u64 = 100_000;
+
+        for step_nr in 1..=MAX_STEPS {
+            if step(&mut grid) == grid.cells().len() as u64 {
+                return Ok(step_nr.into());
+            }
+        }
+
+        Err(crate::Error::NoSolution)
+    }
 }
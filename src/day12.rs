@@ -1,117 +1,168 @@
 use std::collections::{HashMap, HashSet};
 
-const INPUT: &str = include_str!("../problems/problem12");
+use crate::{
+    error::end_of,
+    solution::{Answer, Solution},
+    util::{Memo, UnweightedGraph},
+};
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
-struct Vertex {
+pub struct Vertex {
     // This is quite a greedy optimization, but the number of vertices seems to be way smaller
     // than 256
     id: u8,
     visit_once: bool,
 }
 
-pub fn solve() -> crate::Result<()> {
-    let lines = INPUT.lines();
+pub struct Caves {
+    vertices: HashMap<String, Vertex>,
+    adjacent: HashMap<Vertex, HashSet<Vertex>>,
+}
 
-    let mut vertices: HashMap<String, Vertex> = Default::default();
-    let mut adjacent: HashMap<Vertex, HashSet<Vertex>> = Default::default();
+impl Caves {
+    fn start_and_end(&self) -> crate::Result<(Vertex, Vertex)> {
+        match (self.vertices.get("start"), self.vertices.get("end")) {
+            (Some(&start), Some(&end)) => Ok((start, end)),
+            _ => Err(crate::Error::NoSolution),
+        }
+    }
+}
 
-    let mut get_vertex = |s: &str| {
-        if let Some(v) = vertices.get(s) {
-            *v
-        } else {
-            let s_is_lower = s.chars().all(|c| c.is_lowercase());
+impl UnweightedGraph<Vertex> for Caves {
+    fn adjacent(&self, v: &Vertex, out: &mut Vec<Vertex>) {
+        out.extend(self.adjacent.get(v).into_iter().flatten().copied());
+    }
+}
 
-            let v = Vertex {
-                id: vertices.len() as u8,
-                visit_once: s_is_lower,
-            };
+/// The small caves that have been visited, as a bit set of their ids
+type Visited = [u64; 4];
 
-            vertices.insert(s.to_owned(), v);
+fn is_visited(visited: &Visited, v: Vertex) -> bool {
+    visited[v.id as usize / 64] & (1 << (v.id % 64)) != 0
+}
 
-            v
-        }
-    };
+type PathMemo = Memo<(Vertex, Visited, Option<Vertex>), u64>;
+
+// f(.., (start, visited, visit_twice)) counts the number of paths from start to end that do
+// not enter the visited small caves again. If visit_twice is set, it only counts those paths
+// that visit the passed vertex *exactly* twice.
+fn f(
+    end: Vertex,
+    adjacent: &HashMap<Vertex, HashSet<Vertex>>,
+    memo: &mut PathMemo,
+    (start, mut visited, visit_twice): (Vertex, Visited, Option<Vertex>),
+) -> u64 {
+    if start == end {
+        return visit_twice.is_none() as u64;
+    }
 
-    for line in lines {
-        let mut split = line.trim().split("-");
+    if start.visit_once {
+        visited[start.id as usize / 64] |= 1 << (start.id % 64);
+    }
+
+    let mut ret = 0;
 
-        let v = get_vertex(split.next().ok_or(crate::Error::InvalidInput)?);
-        let w = get_vertex(split.next().ok_or(crate::Error::InvalidInput)?);
+    for &v in &adjacent[&start] {
+        let mut new_visit_twice = visit_twice;
 
-        adjacent.entry(v).or_default().insert(w);
-        adjacent.entry(w).or_default().insert(v);
+        if v.visit_once && is_visited(&visited, v) {
+            if Some(v) == visit_twice {
+                // This vertex is now visited for the second time, but we allow it
+                new_visit_twice = None;
+            } else {
+                // Otherwise, visiting this vertex twice is not allowed
+                continue;
+            }
+        }
+
+        ret += memo.call((v, visited, new_visit_twice), &|memo, key| {
+            f(end, adjacent, memo, key)
+        });
     }
 
-    // f(start, end, ...) counts the number of paths from start to end
-    // if visit twice is set, it only counts those paths that visit the passed vertex *exactly*
-    // twice.
-    fn f(
-        start: Vertex,
-        end: Vertex,
-        visit_twice: Option<Vertex>,
-        path: &mut HashSet<Vertex>,
-        adjacent: &HashMap<Vertex, HashSet<Vertex>>,
-    ) -> u64 {
-        let inserted = path.insert(start);
-
-        let mut ret = 0;
-
-        if start == end {
-            if visit_twice.is_none() {
-                ret = 1;
+    ret
+}
+
+/// The number of paths from start to end, see `f`
+fn count_paths(
+    caves: &Caves,
+    visit_twice: Option<Vertex>,
+    memo: &mut PathMemo,
+) -> crate::Result<u64> {
+    let (start, end) = caves.start_and_end()?;
+    let f = |memo: &mut PathMemo, key| f(end, &caves.adjacent, memo, key);
+
+    Ok(memo.call((start, Visited::default(), visit_twice), &f))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Caves;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let mut vertices: HashMap<String, Vertex> = Default::default();
+        let mut adjacent: HashMap<Vertex, HashSet<Vertex>> = Default::default();
+
+        let mut get_vertex = |s: &str| -> crate::Result<Vertex> {
+            if let Some(v) = vertices.get(s) {
+                Ok(*v)
             } else {
-                // return 0
+                let s_is_lower = s.chars().all(|c| c.is_lowercase());
+
+                let v = Vertex {
+                    id: u8::try_from(vertices.len())
+                        .map_err(|_| crate::Error::parse(input, s, "at most 256 caves"))?,
+                    visit_once: s_is_lower,
+                };
+
+                vertices.insert(s.to_owned(), v);
+
+                Ok(v)
             }
-        } else {
-            for &v in &adjacent[&start] {
-                let mut new_visit_twice = visit_twice;
-
-                if v.visit_once && path.contains(&v) {
-                    if Some(v) == visit_twice {
-                        // This vertex is now visited for the second time, but we allow it
-                        new_visit_twice = None;
-                    } else {
-                        // Otherwise, visiting this vertex twice is not allowed
-                        continue;
-                    }
-                }
-
-                ret += f(v, end, new_visit_twice, path, adjacent);
+        };
+
+        for line in input.lines() {
+            let line = line.trim();
+            let (v, w) = line
+                .split_once('-')
+                .ok_or_else(|| crate::Error::parse(input, end_of(line), "two caves joined by -"))?;
+
+            let v = get_vertex(v)?;
+            let w = get_vertex(w)?;
+
+            // Two adjacent big caves allow infinitely many paths
+            if !v.visit_once && !w.visit_once {
+                return Err(crate::Error::parse(input, line, "at most one big cave per connection"));
             }
-        }
 
-        if inserted {
-            path.remove(&start);
+            adjacent.entry(v).or_default().insert(w);
+            adjacent.entry(w).or_default().insert(v);
         }
 
-        ret
+        Ok(Caves { vertices, adjacent })
     }
 
-    let start = vertices["start"];
-    let end = vertices["end"];
+    fn part1(caves: &Self::Input) -> crate::Result<Answer> {
+        let prob1 = count_paths(caves, None, &mut Memo::new())?;
 
-    let mut temp_path = Default::default();
+        Ok(prob1.into())
+    }
 
-    let prob1 = f(
-        start,
-        end,
-        None,
-        &mut temp_path,
-        &adjacent,
-    );
+    fn part2(caves: &Self::Input) -> crate::Result<Answer> {
+        let (start, end) = caves.start_and_end()?;
 
-    println!("Problem 1: {}", prob1);
+        let mut memo = Memo::new();
 
-    let mut prob2 = prob1;
+        let mut prob2 = count_paths(caves, None, &mut memo)?;
 
-    for &v in vertices.values() {
-        if v.visit_once && v != start && v != end {
-            prob2 += f(start, end, Some(v), &mut temp_path, &adjacent);
+        // Small caves that cannot be reached from the start are not on any path
+        for v in caves.dfs(start) {
+            if v.visit_once && v != start && v != end {
+                prob2 += count_paths(caves, Some(v), &mut memo)?;
+            }
         }
-    }
 
-    println!("Problem 2: {}", prob2);
-
-    Ok(())
+        Ok(prob2.into())
+    }
 }
@@ -1,8 +1,12 @@
 use std::collections::HashSet;
 
-const INPUT: &str = include_str!("../problems/problem13");
+use crate::{
+    error::{end_of, parse_number},
+    solution::{Answer, Solution},
+    util::{BoundingBox, Vec2},
+};
 
-type Point = (u32, u32);
+pub type Point = Vec2<u32>;
 
 fn fold_x(axis_x: u32, grid: &mut HashSet<Point>, width: &mut u32, height: u32) {
     if axis_x >= *width {
@@ -17,8 +21,8 @@ fn fold_x(axis_x: u32, grid: &mut HashSet<Point>, width: &mut u32, height: u32)
         }
 
         for y in 0..height {
-            if grid.contains(&(other_x, y)) {
-                grid.insert((x, y));
+            if grid.contains(&Vec2::new(other_x, y)) {
+                grid.insert(Vec2::new(x, y));
             }
         }
     }
@@ -39,8 +43,8 @@ fn fold_y(axis_y: u32, grid: &mut HashSet<Point>, width: u32, height: &mut u32)
         }
 
         for x in 0..width {
-            if grid.contains(&(x, other_y)) {
-                grid.insert((x, y));
+            if grid.contains(&Vec2::new(x, other_y)) {
+                grid.insert(Vec2::new(x, y));
             }
         }
     }
@@ -48,77 +52,120 @@ fn fold_y(axis_y: u32, grid: &mut HashSet<Point>, width: u32, height: &mut u32)
     *height = axis_y;
 }
 
-fn print_grid(grid: &HashSet<Point>, width: u32, height: u32) {
+fn render_grid(grid: &HashSet<Point>, width: u32, height: u32) -> Vec<String> {
+    let mut rows = Vec::new();
+
     for y in 0..height {
+        let mut row = String::new();
+
         for x in 0..width {
-            if grid.contains(&(x, y)) {
-                print!("#");
+            if grid.contains(&Vec2::new(x, y)) {
+                row.push('#');
             } else {
-                print!(".");
+                row.push('.');
             }
         }
 
-        println!();
+        rows.push(row);
     }
-}
 
-pub fn solve() -> crate::Result<()> {
-    let mut lines = INPUT.lines();
+    rows
+}
 
-    let mut grid: HashSet<Point> = Default::default();
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fold {
+    X(u32),
+    Y(u32),
+}
 
-    let mut max_x = 0;
-    let mut max_y = 0;
+#[derive(Debug, Clone)]
+pub struct Paper {
+    grid: HashSet<Point>,
+    width: u32,
+    height: u32,
+}
 
-    for line in lines.by_ref() {
-        if line.trim().is_empty() {
-            break;
+impl Paper {
+    fn fold(&mut self, fold: Fold) {
+        match fold {
+            Fold::X(axis) => fold_x(axis, &mut self.grid, &mut self.width, self.height),
+            Fold::Y(axis) => fold_y(axis, &mut self.grid, self.width, &mut self.height),
         }
+    }
+}
 
-        let mut split = line.split(',').map(|v| v.parse::<u32>());
+pub struct Day13;
 
-        let x = split.next().ok_or(crate::Error::NoInput)??;
-        let y = split.next().ok_or(crate::Error::NoInput)??;
+impl Solution for Day13 {
+    type Input = (Paper, Vec<Fold>);
 
-        grid.insert((x, y));
-        max_x = std::cmp::max(x, max_x);
-        max_y = std::cmp::max(y, max_y);
-    }
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let mut lines = input.lines();
 
-    let mut width = max_x + 1;
-    let mut height = max_y + 1;
+        let mut grid: HashSet<Point> = Default::default();
 
-    let mut prob1 = None;
+        for line in lines.by_ref() {
+            if line.trim().is_empty() {
+                break;
+            }
+
+            let (x, y) = line
+                .split_once(',')
+                .ok_or_else(|| crate::Error::parse(input, end_of(line), "a point x,y"))?;
 
-    for line in lines {
-        let mut split = line.split('=');
-        let s = split.next();
-        let axis = split
-            .next()
-            .ok_or(crate::Error::InvalidInput)?
-            .parse::<u32>()?;
+            let x = parse_number::<u32>(input, x)?;
+            let y = parse_number::<u32>(input, y)?;
 
-        match s {
-            Some("fold along x") => fold_x(axis, &mut grid, &mut width, height),
-            Some("fold along y") => fold_y(axis, &mut grid, width, &mut height),
-            _ => {}
+            grid.insert(Vec2::new(x, y));
         }
 
-        if prob1.is_none() {
-            let entries = grid
-                .iter()
-                .copied()
-                .filter(|&(x, y)| x <= width && y <= height)
-                .count();
+        let max = BoundingBox::of(grid.iter().copied()).map_or(Vec2::ZERO, |bounds| bounds.max);
+
+        let paper = Paper {
+            grid,
+            width: max.x + 1,
+            height: max.y + 1,
+        };
 
-            prob1 = Some(entries);
+        let mut folds = Vec::new();
+
+        for line in lines {
+            let (s, axis) = line
+                .split_once('=')
+                .ok_or_else(|| crate::Error::parse(input, line, "a fold instruction"))?;
+            let axis = parse_number::<u32>(input, axis)?;
+
+            match s {
+                "fold along x" => folds.push(Fold::X(axis)),
+                "fold along y" => folds.push(Fold::Y(axis)),
+                _ => return Err(crate::Error::parse(input, s, "fold along x or fold along y")),
+            }
         }
+
+        Ok((paper, folds))
     }
 
-    println!("Problem 1: {}", prob1.unwrap());
+    fn part1((paper, folds): &Self::Input) -> crate::Result<Answer> {
+        let mut paper = paper.clone();
+        paper.fold(*folds.first().ok_or(crate::Error::NoInput)?);
+
+        let entries = paper
+            .grid
+            .iter()
+            .copied()
+            .filter(|p| p.x <= paper.width && p.y <= paper.height)
+            .count();
 
-    println!("Problem 2:");
-    print_grid(&grid, width, height);
+        Ok(entries.into())
+    }
 
-    Ok(())
-}
\ No newline at end of file
+    fn part2((paper, folds): &Self::Input) -> crate::Result<Answer> {
+        let mut paper = paper.clone();
+
+        for &fold in folds {
+            paper.fold(fold);
+        }
+
+        Ok(Answer::Grid(render_grid(&paper.grid, paper.width, paper.height)))
+    }
+}
@@ -1,98 +1,107 @@
 use std::collections::{HashMap, HashSet};
 
-const INPUT: &str = include_str!("../problems/problem14");
+use crate::{
+    solution::{Answer, Solution},
+    util::Memo,
+};
 
-pub fn solve() -> crate::Result<()> {
-    let mut lines = INPUT.lines();
+pub type Rules = HashMap<(char, char), char>;
 
-    let input = lines
-        .next()
-        .ok_or(crate::Error::NoInput)?
-        .chars()
-        .collect::<Vec<char>>();
+type PairMemo = Memo<(char, char, char, usize), u64>;
 
-    // Skip the following empty line
-    let _ = lines.next();
+// f(rules, memo, (a, b, c, steps)) is the number of c's that are *added* to (a, b) after
+// expanding it steps number of steps.
+fn f(rules: &Rules, memo: &mut PairMemo, (a, b, c, steps): (char, char, char, usize)) -> u64 {
+    let mut ret = 0;
 
-    let rules = lines
-        .map(|line| -> Option<((char, char), char)> {
-            let mut s = line.split(" -> ");
-
-            let mut lhs = s.next()?.chars();
-            let (lhs1, lhs2) = (lhs.next()?, lhs.next()?);
+    if steps > 0 {
+        if let Some(&x) = rules.get(&(a, b)) {
+            if x == c {
+                ret += 1;
+            }
 
-            let rhs = s.next()?.chars().next()?;
+            // The pattern will turn into a x b so now we analyze a x and x b
+            let f = |memo: &mut PairMemo, key| f(rules, memo, key);
+            ret += memo.call((a, x, c, steps - 1), &f) + memo.call((x, b, c, steps - 1), &f);
+        }
+    }
 
-            Some(((lhs1, lhs2), rhs))
-        })
-        .collect::<Option<HashMap<(char, char), char>>>()
-        .ok_or(crate::Error::InvalidInput)?;
+    ret
+}
 
+/// The difference between the quantities of the most and the least common element
+/// after expanding the template the given number of steps
+fn solve_steps(input: &[char], rules: &Rules, steps: usize) -> crate::Result<u64> {
     let all_chars: HashSet<char> = input
         .iter()
         .copied()
         .chain(rules.values().copied())
         .collect();
 
-    // f((a, b), c, steps, ...) is the number of c's that are *added* to (a, b) after expanding it
-    // steps number of steps.
-    fn f(
-        (a, b): (char, char),
-        c: char,
-        steps: usize,
-        rules: &HashMap<(char, char), char>,
-        cache: &mut HashMap<(char, char, char, usize), u64>,
-    ) -> u64 {
-        if let Some(&ret) = cache.get(&(a, b, c, steps)) {
-            return ret;
-        }
+    let mut memo = Memo::new();
 
-        let mut ret = 0;
+    let mut qtys: HashMap<char, u64> = Default::default();
+    for &c in input {
+        *qtys.entry(c).or_insert(0) += 1;
+    }
 
-        if steps > 0 {
-            if let Some(&x) = rules.get(&(a, b)) {
-                if x == c {
-                    ret += 1;
-                }
+    for c in all_chars {
+        for pair in input.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
 
-                // The pattern will turn into a x b so now we analyze a x and x b
-                ret +=
-                    f((a, x), c, steps - 1, rules, cache) + f((x, b), c, steps - 1, rules, cache);
-            }
+            *qtys.entry(c).or_insert(0) +=
+                memo.call((a, b, c, steps), &|memo, key| f(rules, memo, key));
         }
+    }
 
-        cache.insert((a, b, c, steps), ret);
+    let max = qtys.values().max().ok_or(crate::Error::NoInput)?;
+    let min = qtys.values().min().ok_or(crate::Error::NoInput)?;
 
-        ret
-    }
+    Ok(max - min)
+}
 
-    let mut cache = Default::default();
+pub struct Day14;
 
-    let mut base_qty: HashMap<char, u64> = Default::default();
-    for &c in &input {
-        *base_qty.entry(c).or_insert(0) += 1;
-    }
+impl Solution for Day14 {
+    type Input = (Vec<char>, Rules);
 
-    let mut qtys1 = base_qty.clone();
-    let mut qtys2 = base_qty;
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let mut lines = input.lines();
 
-    for c in all_chars {
-        for i in 0..input.len() - 1 {
-            let (a, b) = (input[i], input[i + 1]);
+        let template = lines
+            .next()
+            .ok_or(crate::Error::NoInput)?
+            .chars()
+            .collect::<Vec<char>>();
 
-            *qtys2.entry(c).or_insert(0) += f((a, b), c, 40, &rules, &mut cache);
-            *qtys1.entry(c).or_insert(0) += f((a, b), c, 10, &rules, &mut cache);
-        }
+        // Skip the following empty line
+        let _ = lines.next();
+
+        let rules = lines
+            .map(|line| -> crate::Result<((char, char), char)> {
+                let rule = || -> Option<((char, char), char)> {
+                    let mut s = line.split(" -> ");
+
+                    let mut lhs = s.next()?.chars();
+                    let (lhs1, lhs2) = (lhs.next()?, lhs.next()?);
+
+                    let rhs = s.next()?.chars().next()?;
+
+                    Some(((lhs1, lhs2), rhs))
+                };
+
+                rule().ok_or_else(|| crate::Error::parse(input, line, "a rule like AB -> C"))
+            })
+            .collect::<crate::Result<Rules>>()?;
+
+        Ok((template, rules))
     }
 
-    println!(
-        "Problem 1: {}",
-        qtys1.values().max().unwrap() - qtys1.values().min().unwrap()
-    );
-    println!(
-        "Problem 2: {}",
-        qtys2.values().max().unwrap() - qtys2.values().min().unwrap()
-    );
+    fn part1((input, rules): &Self::Input) -> crate::Result<Answer> {
+        Ok(solve_steps(input, rules, 10)?.into())
+    }
 
-    Ok(())
+    fn part2((input, rules): &Self::Input) -> crate::Result<Answer> {
+        Ok(solve_steps(input, rules, 40)?.into())
+    }
 }
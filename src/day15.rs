@@ -1,104 +1,116 @@
 use crate::{
-    util::{Edge, Graph},
+    solution::{Answer, Solution},
+    solvers::Part,
+    util::{Backend, DenseGraph, Graph, Grid, Neighborhood, Path},
 };
 
-const INPUT: &str = include_str!("../problems/problem15");
+pub struct RiskMap {
+    grid: Grid<u8>,
+}
+
+/// The cave of the given part: the map itself for the first part, and the map repeated five
+/// times in both directions, with increasing risk levels, for the second one
+fn cave(map: &RiskMap, part: Part) -> Grid<u8> {
+    let grid = &map.grid;
 
-fn checked_add(a: usize, b: isize) -> Option<usize> {
-    if b >= 0 {
-        a.checked_add(b as usize)
-    } else {
-        a.checked_sub(-b as usize)
+    match part {
+        Part::One => grid.clone(),
+        Part::Two => Grid::from_fn(5 * grid.width(), 5 * grid.height(), |x, y| {
+            let (width, height) = (grid.width(), grid.height());
+
+            let base_risk = grid[(x % width, y % height)];
+            let i = (x / width + y / height) as u8;
+            (base_risk - 1 + i) % 9 + 1
+        }),
     }
 }
 
-fn in_bounds(
-    x: usize,
-    y: usize,
-    dx: isize,
-    dy: isize,
-    width: usize,
-    height: usize,
-) -> Option<(usize, usize)> {
-    let nx = checked_add(x, dx)?;
-    let ny = checked_add(y, dy)?;
-
-    if nx < width && ny < height {
-        Some((nx, ny))
-    } else {
-        None
-    }
+/// A lower bound for the risk of getting from position v to w, given that entering any
+/// position costs at least `min_risk`
+fn estimate(cave: &Grid<u8>, v: usize, w: usize, min_risk: u64) -> u64 {
+    let (x1, y1) = cave.position_of(v);
+    let (x2, y2) = cave.position_of(w);
+
+    (x1.abs_diff(x2) + y1.abs_diff(y2)) as u64 * min_risk
 }
 
-pub fn solve() -> crate::Result<()> {
-    let lines = INPUT.lines();
+/// The lowest total risk of any path from the top left to the bottom right corner of the
+/// cave of the given part, computed with the given backend. The hashed one uses A*.
+pub fn lowest_risk(map: &RiskMap, part: Part, backend: Backend) -> crate::Result<u64> {
+    let cave = cave(map, part);
+    let graph = cave.graph(Neighborhood::Four, |&risk| Some(risk as u64));
+    let end = cave.cells().len() - 1;
 
-    let mut grid = Vec::new();
-    let mut width = 0;
+    let risk = match backend {
+        Backend::Hashed => {
+            let min_risk = cave.iter().copied().min().unwrap_or(1) as u64;
+            let heuristic = |&v: &usize| estimate(&cave, v, end, min_risk);
+            let (dist, _) = graph.a_star(0, Some(end), Some(&heuristic), false);
 
-    for line in lines {
-        if width == 0 {
-            width = line.len();
+            dist.get(&end).copied()
         }
+        Backend::Dense => {
+            let (dist, _) = graph.dense_dijkstra(0, Some(end), false);
 
-        for c in line.chars() {
-            grid.push(c.to_digit(10).ok_or(crate::Error::InvalidInput)? as u8);
+            Some(dist[end]).filter(|&d| d != u64::MAX)
         }
-    }
+    };
 
-    let width = width;
-    let height = grid.len() / width;
+    risk.ok_or(crate::Error::NoSolution)
+}
 
-    struct GridGraph<F: Fn(usize, usize) -> u64> {
-        cost: F,
-        width: usize,
-        height: usize,
-    }
+/// The path with the lowest total risk from the top left to the bottom right corner of the
+/// cave of the given part. Vertices are positions `x + y * width`.
+pub fn safest_path(map: &RiskMap, part: Part) -> crate::Result<Path<usize>> {
+    let cave = cave(map, part);
+    let graph = cave.graph(Neighborhood::Four, |&risk| Some(risk as u64));
+    let end = cave.cells().len() - 1;
 
-    impl <F: Fn(usize, usize) -> u64> Graph<usize> for GridGraph<F> {
-        fn neighbors(&self, &v: &usize) -> Vec<Edge<usize>> {
-            let mut ret = Vec::new();
-            let (x, y) = (v % self.width, v / self.width);
-            
-            for &(dx, dy) in &[(-1, 0), (1, 0), (0, -1), (0, 1)] {
-                if let Some((nx, ny)) = in_bounds(x, y, dx, dy, self.width, self.height) {
-                    let vertex = nx + ny * self.width;
-                    let cost = (self.cost)(nx, ny);
-
-                    ret.push(Edge { vertex, cost });
-                }
-            }
-
-            ret
-        }
+    let min_risk = cave.iter().copied().min().unwrap_or(1) as u64;
+    let heuristic = |&v: &usize| estimate(&cave, v, end, min_risk);
+
+    graph
+        .shortest_path(0, end, Some(&heuristic))
+        .ok_or(crate::Error::NoSolution)
+}
+
+/// Draw the cave of the given part with the risk levels along the path, all other positions
+/// are shown as dots
+pub fn render_path(map: &RiskMap, part: Part, path: &Path<usize>) -> Vec<String> {
+    let cave = cave(map, part);
+
+    let mut shown = cave.map(|_| '.');
+    for &v in &path.vertices {
+        shown.cells_mut()[v] = char::from(b'0' + cave.cells()[v]);
     }
 
-    // Part 1
-    let graph1 = GridGraph {
-        cost: (|x, y| grid[x + y * width] as u64),
-        width, height
-    };
+    shown.to_string().lines().map(str::to_owned).collect()
+}
 
-    let end1 = graph1.width * graph1.height - 1;
-    let (dist1, _) =  graph1.dijsktra(0, Some(end1), false);
-    println!("Problem 1: {}", dist1[&end1]);
+pub struct Day15;
 
-    // Part 2
+impl Solution for Day15 {
+    type Input = RiskMap;
 
-    let modified_cost = |x: usize, y: usize| {
-        let base_cost = grid[(x % width) + (y % height) * width] as u64;
-        let i = (x / width) as u64 + (y / height) as u64;
-        (base_cost - 1 + i) % 9 + 1
-    };
-    let graph2 = GridGraph {
-        cost: modified_cost,
-        width: 5 * width,
-        height: 5 * height
-    };
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let grid = Grid::parse(input, "a risk level from 1 to 9", |c| {
+            c.to_digit(10).filter(|&d| d > 0).map(|d| d as u8)
+        })?;
+
+        Ok(RiskMap { grid })
+    }
+
+    fn part1(map: &Self::Input) -> crate::Result<Answer> {
+        Ok(lowest_risk(map, Part::One, Backend::Dense)?.into())
+    }
 
-    let end2 = graph2.width * graph2.height - 1;
-    let (dist2, _) =  graph2.dijsktra(0, Some(end2), false);
-    println!("Problem 2: {}", dist2[&end2]);
+    fn part2(map: &Self::Input) -> crate::Result<Answer> {
+        Ok(lowest_risk(map, Part::Two, Backend::Dense)?.into())
+    }
+
+    fn explain(map: &Self::Input, part: Part) -> crate::Result<Vec<String>> {
+        let path = safest_path(map, part)?;
 
-    Ok(())
+        Ok(render_path(map, part, &path))
+    }
 }
@@ -1,39 +1,43 @@
-const INPUT: &str = include_str!("../problems/problem16");
-
-fn hex_digit_to_bits(d: char) -> Option<&'static [u8]> {
-    const DIGITS: [[u8; 4]; 16] = [
-        [0, 0, 0, 0], // 0
-        [0, 0, 0, 1], // 1
-        [0, 0, 1, 0], // 2
-        [0, 0, 1, 1], // 3
-        [0, 1, 0, 0], // 4
-        [0, 1, 0, 1], // 5
-        [0, 1, 1, 0], // 6
-        [0, 1, 1, 1], // 7
-        [1, 0, 0, 0], // 8
-        [1, 0, 0, 1], // 9
-        [1, 0, 1, 0], // A
-        [1, 0, 1, 1], // B
-        [1, 1, 0, 0], // C
-        [1, 1, 0, 1], // D
-        [1, 1, 1, 0], // E
-        [1, 1, 1, 1], // F
+use crate::{
+    error::Parsed,
+    solution::{Answer, Solution},
+    util::parse::{count, end, literal, many, many1, map, pair, preceded, take, terminated, try_map, Parser},
+};
+
+fn hex_digit_to_bits(d: char) -> Option<&'static str> {
+    const DIGITS: [&str; 16] = [
+        "0000", // 0
+        "0001", // 1
+        "0010", // 2
+        "0011", // 3
+        "0100", // 4
+        "0101", // 5
+        "0110", // 6
+        "0111", // 7
+        "1000", // 8
+        "1001", // 9
+        "1010", // A
+        "1011", // B
+        "1100", // C
+        "1101", // D
+        "1110", // E
+        "1111", // F
     ];
 
     let d = d.to_digit(16)? as usize;
 
-    Some(&DIGITS[d])
+    Some(DIGITS[d])
 }
 
 #[derive(Debug, Clone)]
-struct Packet {
-    version: u64,
-    type_id: u64,
-    payload: Payload,
+pub struct Packet {
+    pub version: u64,
+    pub type_id: u64,
+    pub payload: Payload,
 }
 
 #[derive(Debug, Clone)]
-enum Payload {
+pub enum Payload {
     Literal(u64),
     Subpackets(Packets)
 }
@@ -45,7 +49,7 @@ enum Payload {
 // Here, it is not.
 
 #[derive(Debug, Clone)]
-enum Packets {
+pub enum Packets {
     One(Box<Packet>),
     More(Box<Packet>, Box<Packets>)
 }
@@ -61,7 +65,7 @@ impl <'a> IntoIterator for &'a Packets {
     }
 }
 
-struct PacketsIterator<'a> {
+pub struct PacketsIterator<'a> {
     current: Option<&'a Packets>
 }
 
@@ -73,208 +77,162 @@ impl <'a> Iterator for PacketsIterator<'a> {
             Some(Packets::One(packet)) => {
                 self.current = None;
 
-                Some(&packet)
+                Some(packet)
             },
             Some(Packets::More(packet, more)) => {
-                self.current = Some(&more);
+                self.current = Some(more);
 
-                Some(&packet)
+                Some(packet)
             },
             _ => None
         }
     }
 }
 
-fn parse_number(bits: &[u8], n: usize) -> Option<(u64, &[u8])> {
-    let mut res = 0;
+// The packets are parsed from a string of the characters 0 and 1
 
-    if bits.len() < n {
-        return None;
-    }
-
-    let (nr, rem) = bits.split_at(n);
-
-    for &b in nr {
-        res *= 2;
-        res += b as u64;
-    }
-
-    Some((res, rem))
+/// A number of n bits
+fn number<'a>(n: usize) -> impl Parser<'a, u64> {
+    try_map(take(n), "a number", |bits| u64::from_str_radix(bits, 2).ok())
 }
 
-fn parse_bit(bits: &[u8]) -> Option<(u8, &[u8])> {
-    if bits.len() > 0 {
-        return Some((bits[0], &bits[1..]));
-    } else {
-        None
-    }
+fn parse_literal(bits: &str) -> Parsed<'_, u64> {
+    // Groups of four bits, all but the last one are prefixed by a 1
+    let groups = pair(
+        many(preceded(literal("1"), number(4))),
+        preceded(literal("0"), number(4)),
+    );
+
+    try_map(groups, "a literal of at most 64 bits", |(groups, last)| {
+        groups
+            .into_iter()
+            .chain(std::iter::once(last))
+            .try_fold(0u64, |res, group| res.checked_mul(1 << 4)?.checked_add(group))
+    })(bits)
 }
 
-fn parse_literal(mut bits: &[u8]) -> Option<(u64, &[u8])> {
-    if bits.len() == 0 {
-        return None;
-    }
+/// The subpackets of an operator packet, preceded by their total length in bits or their count
+fn parse_subpackets(bits: &str) -> Parsed<'_, Packets> {
+    let (length_type_id, bits) = number(1)(bits)?;
 
-    let mut res = 0;
+    let (packets, rem) = if length_type_id == 0 {
+        let (length, bits) = number(15)(bits)?;
+        let (bits, rem) = take(length as usize)(bits)?;
+        let (packets, _) = terminated(many1(parse_packet), end)(bits)?;
 
-    loop {
-        let (read_next, rem) = parse_bit(bits)?;
-        bits = rem;
+        (packets, rem)
+    } else {
+        let (n, bits) = number(11)(bits)?;
+        count(n as usize, parse_packet)(bits)?
+    };
 
-        let (number, rem) = parse_number(bits, 4)?;
-        bits = rem;
+    // Build the list from the back
+    let mut packets = packets.into_iter().rev();
+    let last = packets.next().ok_or((bits, "at least one subpacket"))?;
 
-        res *= 1 << 4;
-        res += number;
+    let subpackets = packets.fold(Packets::One(last.into()), |more, packet| {
+        Packets::More(packet.into(), more.into())
+    });
 
-        if read_next == 0 {
-            break;
-        }
-    }
-
-    Some((res, bits))
+    Ok((subpackets, rem))
 }
 
-fn parse_subpackets(bits: &[u8], n: Option<usize>) -> Option<(Packets, &[u8])> {
-    if n == Some(0) {
-        return None;
-    }
+fn parse_packet(bits: &str) -> Parsed<'_, Packet> {
+    let ((version, type_id), bits) = pair(number(3), number(3))(bits)?;
 
-    let (packet, rem1) = parse_packet(bits)?;
+    let (payload, rem) = if type_id == 4 {
+        map(parse_literal, Payload::Literal)(bits)?
+    } else {
+        map(parse_subpackets, Payload::Subpackets)(bits)?
+    };
 
-    if let Some((subpackets, rem2)) = parse_subpackets(rem1, n.map(|n| n - 1)) {
-        let sub = Packets::More(packet.into(), subpackets.into());
+    let packet = Packet {
+        version,
+        type_id,
+        payload,
+    };
 
-        Some((sub, rem2))
-    } else {
-        let sub = Packets::One(packet.into());
-        Some((sub, rem1))
-    }
+    Ok((packet, rem))
 }
 
-fn parse_packet(bits: &[u8]) -> Option<(Packet, &[u8])> {
-    let (version, bits) = parse_number(bits, 3)?;
-    let (type_id, bits) = parse_number(bits, 3)?;
+/// Evaluate the expression of a packet. Fails for unknown type ids and comparisons that do
+/// not have exactly two operands.
+pub fn evaluate(p: &Packet) -> crate::Result<u64> {
+    match &p.payload {
+        Payload::Literal(l) => Ok(*l),
+        Payload::Subpackets(subpackets) => {
+            let values = subpackets
+                .into_iter()
+                .map(evaluate)
+                .collect::<crate::Result<Vec<_>>>()?;
+
+            let compare = |f: fn(&u64, &u64) -> bool| match values[..] {
+                [a, b] => Ok(f(&a, &b) as u64),
+                _ => Err(crate::Error::InvalidInput),
+            };
 
-    if type_id == 4 {
-        // Literal packet
+            match p.type_id {
+                0 => Ok(values.iter().sum()),
+                1 => Ok(values.iter().product()),
+                2 => values.iter().copied().min().ok_or(crate::Error::InvalidInput),
+                3 => values.iter().copied().max().ok_or(crate::Error::InvalidInput),
+                5 => compare(u64::gt),
+                6 => compare(u64::lt),
+                7 => compare(u64::eq),
+                _ => Err(crate::Error::InvalidInput),
+            }
+        }
+    }
+}
 
-        let (literal, bits) = parse_literal(bits)?;
-        let packet = Packet {
-            version,
-            type_id,
-            payload: Payload::Literal(literal),
-        };
+/// The sum of the version numbers of a packet and all its subpackets
+pub fn version_sum(p: &Packet) -> u64 {
+    match &p.payload {
+        &Payload::Literal(_) => p.version,
+        Payload::Subpackets(subpackets) => {
+            p.version + subpackets.into_iter()
+                .map(version_sum)
+                .sum::<u64>()
+        }
+    }
+}
 
-        Some((packet, bits))
-    } else {
-        // Operator packet
-        let (lenght_type_id, bits) = parse_bit(bits)?;
+pub struct Day16;
 
-        if lenght_type_id == 0 {
-            let (length, bits) = parse_number(bits, 15)?;
-            let (bits, rem_all) = bits.split_at(length as usize);
+impl Solution for Day16 {
+    type Input = Packet;
 
-            let (subpackets, rem ) = parse_subpackets(bits, None)?;
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let line = input
+            .lines()
+            .next()
+            .ok_or(crate::Error::NoInput)?;
 
-            if rem.len() != 0 {
-                return None;
-            }
+        let line = line.trim();
+        let mut bits = String::with_capacity(4 * line.len());
 
-            let packet = Packet {
-                version,
-                type_id,
-                payload: Payload::Subpackets(subpackets),
-            };
+        for (i, c) in line.char_indices() {
+            let digit = hex_digit_to_bits(c).ok_or_else(|| {
+                crate::Error::parse(input, &line[i..i + c.len_utf8()], "a hexadecimal digit")
+            })?;
 
-            Some((packet, rem_all))
-        } else {
-            let (no, bits) = parse_number(bits, 11)?;
+            bits.push_str(digit);
+        }
 
-            let (subpackets, rem) = parse_subpackets(bits, Some(no as usize))?;
+        // We only treat the case here, where there is one outermost packet and all other packets
+        // are subpackets of it. This seems to be the case (and should be the case because of part 2).
 
-            let packet = Packet {
-                version,
-                type_id,
-                payload: Payload::Subpackets(subpackets),
-            };
+        let (outer_packet, _) = parse_packet(&bits)
+            .map_err(|_| crate::Error::parse(input, line, "a complete packet"))?;
 
-            Some((packet, rem))
-        }
+        Ok(outer_packet)
     }
-}
 
-pub fn solve() -> crate::Result<()> {
-    let line = INPUT
-        .lines()
-        .next()
-        .ok_or(crate::Error::NoInput)?;
-
-    let bits = line
-        .chars()
-        .filter_map(|d| hex_digit_to_bits(d))
-        .flatten()
-        .copied()
-        .collect::<Vec<_>>();
-
-    let bits = bits.as_slice();
-
-    // We only treat the case here, where there is one outermost packet and all other packets
-    // are subpackets of it. This seems to be the case (and should be the case because of part 2).
-
-    let (outer_packet, _) = parse_packet(bits).ok_or(crate::Error::InvalidInput)?;
-
-    fn evaluate(p: &Packet) -> u64 {
-        match &p.payload {
-            Payload::Literal(l) => *l,
-            Payload::Subpackets(subpackets) => {
-                let mut subpackets = subpackets.into_iter();
-
-                match p.type_id {
-                    0 => subpackets.map(|p| evaluate(p)).sum(),
-                    1 => subpackets.map(|p| evaluate(p)).product(),
-                    2 => subpackets.map(|p| evaluate(p)).min().unwrap(),
-                    3 => subpackets.map(|p| evaluate(p)).max().unwrap(),
-                    5 => {
-                        // Greater than
-                        let p1 = subpackets.next().unwrap();
-                        let p2 = subpackets.next().unwrap();
-
-                        (evaluate(p1) > evaluate(p2)) as u64
-                    }
-                    6 => {
-                        // Less than
-                        let p1 = subpackets.next().unwrap();
-                        let p2 = subpackets.next().unwrap();
-                        
-                        (evaluate(p1) < evaluate(p2)) as u64
-                    }
-                    7 => {
-                        // Equal
-                        let p1 = subpackets.next().unwrap();
-                        let p2 = subpackets.next().unwrap();
-
-                        (evaluate(p1) == evaluate(p2)) as u64
-                    },
-                    _ => panic!("invalid type id")
-                }
-            }
-        }
+    fn part1(outer_packet: &Self::Input) -> crate::Result<Answer> {
+        Ok(version_sum(outer_packet).into())
     }
 
-    fn version_sum(p: &Packet) -> u64 {
-        match &p.payload {
-            &Payload::Literal(_) => p.version,
-            Payload::Subpackets(subpackets) => {
-                p.version + (&subpackets).into_iter()
-                    .map(|p| version_sum(p))
-                    .sum::<u64>()
-            }
-        }
+    fn part2(outer_packet: &Self::Input) -> crate::Result<Answer> {
+        Ok(evaluate(outer_packet)?.into())
     }
-
-    println!("Problem 1: {}", version_sum(&outer_packet));
-    println!("Problem 2: {}", evaluate(&outer_packet));
-
-    Ok(())
 }
@@ -1,34 +1,25 @@
 use std::collections::HashSet;
 
-const INPUT: &str = include_str!("../problems/problem17");
-
-fn parse_range(s: &str) -> Option<(i64, i64)> {
-    let mut split = s.split("..");
-
-    let start = split.next()?.parse::<i64>().ok()?;
-    let end = split.next()?.parse::<i64>().ok()?;
-
-    Some((start, end))
+use crate::{
+    solution::{Answer, Solution},
+    util::{
+        parse::{integer, literal, pair, parse_all, preceded, terminated, Parser},
+        BoundingBox, Vec2,
+    },
+};
+
+/// A range like "x=20..30", where the prefix is "x="
+fn range<'a>(prefix: &'static str) -> impl Parser<'a, (i64, i64)> {
+    preceded(literal(prefix), pair(terminated(integer(), literal("..")), integer()))
 }
 
-pub fn solve() -> crate::Result<()> {
-    let line = INPUT
-        .lines()
-        .next()
-        .ok_or(crate::Error::NoInput)?;
-
-    let line = &line["target area: ".len()..];
-    let mut split = line.split(", ");
-
-    let x_range = &split.next().ok_or(crate::Error::InvalidInput)?["x=".len()..];
-    let y_range = &split.next().ok_or(crate::Error::InvalidInput)?["x=".len()..];
-
-    let (start_x, end_x): (i64, i64) = parse_range(x_range).ok_or(crate::Error::InvalidInput)?;
-    let (start_y, end_y): (i64, i64) = parse_range(y_range).ok_or(crate::Error::InvalidInput)?;
+/// The target area
+pub type Target = BoundingBox<Vec2<i64>>;
 
-    let mut possible_velocities: HashSet<(i64, i64)> = Default::default();
+fn possible_velocities(target: &Target) -> HashSet<Vec2<i64>> {
+    let mut possible_velocities: HashSet<Vec2<i64>> = Default::default();
 
-    for y in start_y..=end_y {
+    for y in target.min.y..=target.max.y {
         // Determine all the (t, v0_y) such that the probe will
         // land in y at step t.
 
@@ -50,7 +41,7 @@ pub fn solve() -> crate::Result<()> {
             }
 
             // Now, try and find a matching v0_x
-            for v0_x in 0..=end_x {
+            for v0_x in 0..=target.max.x {
                 let sx = if v0_x >= t {
                     v0_x * t - (t * t - t) / 2
                 } else {
@@ -58,20 +49,52 @@ pub fn solve() -> crate::Result<()> {
                     (v0_x * v0_x + v0_x) / 2
                 };
 
-                if start_x <= sx && sx <= end_x {
-                    possible_velocities.insert((v0_x, v0_y));
+                if target.min.x <= sx && sx <= target.max.x {
+                    possible_velocities.insert(Vec2::new(v0_x, v0_y));
                 }
             }
         }
     }
 
-    let max_y_velocity = possible_velocities
-        .iter()
-        .map(|(_, vy)| (vy * vy + vy) / 2)
-        .max();
+    possible_velocities
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Target;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let line = input
+            .lines()
+            .next()
+            .ok_or(crate::Error::NoInput)?;
+
+        let ((start_x, end_x), (start_y, end_y)) = parse_all(
+            input,
+            line,
+            preceded(
+                literal("target area: "),
+                pair(range("x="), preceded(literal(", "), range("y="))),
+            ),
+        )?;
+
+        Ok(BoundingBox {
+            min: Vec2::new(start_x, start_y),
+            max: Vec2::new(end_x, end_y),
+        })
+    }
 
-    println!("Problem 1: {}", max_y_velocity.unwrap());
-    println!("Problem 2: {}", possible_velocities.len());
+    fn part1(target: &Self::Input) -> crate::Result<Answer> {
+        let max_y_velocity = possible_velocities(target)
+            .iter()
+            .map(|v| (v.y * v.y + v.y) / 2)
+            .max();
 
-    Ok(())
+        Ok(max_y_velocity.ok_or(crate::Error::NoSolution)?.into())
+    }
+
+    fn part2(target: &Self::Input) -> crate::Result<Answer> {
+        Ok(possible_velocities(target).len().into())
+    }
 }
@@ -1,9 +1,13 @@
-const INPUT: &str = include_str!("../problems/problem18");
+use crate::{
+    error::Parsed,
+    solution::{Answer, Solution},
+    util::parse::{alt, delimited, digit, label, literal, map, pair, parse_all, terminated},
+};
 
 #[derive(Debug, Clone)]
-struct SnailfishNumber {
-    left: Number,
-    right: Number,
+pub struct SnailfishNumber {
+    pub left: Number,
+    pub right: Number,
 }
 
 impl std::fmt::Display for SnailfishNumber {
@@ -24,7 +28,8 @@ impl std::ops::Add for SnailfishNumber {
 }
 
 impl SnailfishNumber {
-    fn reduce(&mut self) {
+    /// Explode and split until neither applies anymore
+    pub fn reduce(&mut self) {
         while self.reduce_once() {}
     }
 
@@ -159,13 +164,18 @@ impl SnailfishNumber {
         }
     }
 
-    fn magnitude(&self) -> u64 {
+    pub fn magnitude(&self) -> u64 {
         3 * self.left.magnitude() + 2 * self.right.magnitude()
     }
+
+    /// The number of pairs this number is nested in, counting itself
+    pub fn depth(&self) -> usize {
+        1 + std::cmp::max(self.left.depth(), self.right.depth())
+    }
 }
 
 #[derive(Debug, Clone)]
-enum Number {
+pub enum Number {
     Literal(u8),
     Other(Box<SnailfishNumber>),
 }
@@ -177,6 +187,13 @@ impl Number {
             Number::Other(x) => x.magnitude(),
         }
     }
+
+    fn depth(&self) -> usize {
+        match self {
+            Number::Literal(_) => 0,
+            Number::Other(x) => x.depth(),
+        }
+    }
 }
 
 impl std::fmt::Display for Number {
@@ -188,68 +205,70 @@ impl std::fmt::Display for Number {
     }
 }
 
-fn parse_snailfish_number(s: &str) -> Option<(SnailfishNumber, &str)> {
-    if !s.starts_with('[') {
-        return None;
-    }
+fn parse_snailfish_number(s: &str) -> Parsed<'_, SnailfishNumber> {
+    let pair_of_numbers = delimited(
+        literal("["),
+        pair(terminated(parse_number, literal(",")), parse_number),
+        literal("]"),
+    );
 
-    let (left, rem) = parse_number(&s[1..])?;
+    map(pair_of_numbers, |(left, right)| SnailfishNumber { left, right })(s)
+}
 
-    if !rem.starts_with(',') {
-        return None;
-    }
+fn parse_number(s: &str) -> Parsed<'_, Number> {
+    let regular = map(digit, |d| Number::Literal(d as u8));
+    let nested = map(parse_snailfish_number, |n| Number::Other(n.into()));
 
-    let (right, rem) = parse_number(&rem[1..])?;
-    let sn = SnailfishNumber { left, right };
+    label(alt(regular, nested), "a digit or [")(s)
+}
 
-    if !rem.starts_with(']') {
-        return None;
-    }
+pub struct Day18;
 
-    return Some((sn, &rem[1..]));
-}
+impl Solution for Day18 {
+    type Input = Vec<SnailfishNumber>;
 
-fn parse_number(s: &str) -> Option<(Number, &str)> {
-    if let Some((n, rem)) = parse_snailfish_number(s) {
-        Some((Number::Other(n.into()), rem))
-    } else {
-        let parsed_digit = s.chars().next()?.to_digit(10)? as u8;
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        input
+            .lines()
+            .map(|s| {
+                let n = parse_all(input, s, parse_snailfish_number)?;
 
-        Some((Number::Literal(parsed_digit), &s[1..]))
-    }
-}
+                // Reducing assumes that only pairs of regular numbers are nested that deep
+                if n.depth() > 4 {
+                    return Err(crate::Error::parse(input, s, "pairs nested at most four deep"));
+                }
 
-pub fn solve() -> crate::Result<()> {
-    let lines = INPUT.lines();
-    let numbers = lines
-        .map(|s| parse_snailfish_number(&s).map(|(n, _)| n))
-        .collect::<Option<Vec<_>>>()
-        .ok_or(crate::Error::InvalidInput)?;
-
-    let mut res = numbers.first().ok_or(crate::Error::NoInput)?.clone();
-    for x in &numbers[1..] {
-        res = res + x.clone();
-        res.reduce();
+                Ok(n)
+            })
+            .collect()
     }
 
-    println!("Problem 1: {}", res.magnitude());
+    fn part1(numbers: &Self::Input) -> crate::Result<Answer> {
+        let mut res = numbers.first().ok_or(crate::Error::NoInput)?.clone();
+        for x in &numbers[1..] {
+            res = res + x.clone();
+            res.reduce();
+        }
 
-    let mut max_magnitude = 0;
+        Ok(res.magnitude().into())
+    }
 
-    for i in 0..numbers.len() {
-        for j in 0..numbers.len() {
-            if i == j {
-                continue;
-            }
+    fn part2(numbers: &Self::Input) -> crate::Result<Answer> {
+        let mut max_magnitude = 0;
 
-            let mut res = numbers[i].clone() + numbers[j].clone();
-            res.reduce();
+        for i in 0..numbers.len() {
+            for j in 0..numbers.len() {
+                if i == j {
+                    continue;
+                }
 
-            max_magnitude = std::cmp::max(res.magnitude(), max_magnitude);
-        }
-    }
+                let mut res = numbers[i].clone() + numbers[j].clone();
+                res.reduce();
 
-    println!("Problem 2: {}", max_magnitude);
+                max_magnitude = std::cmp::max(res.magnitude(), max_magnitude);
+            }
+        }
 
-    Ok(())
+        Ok(max_magnitude.into())
+    }
 }
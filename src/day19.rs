@@ -1,98 +1,32 @@
-use std::{
-    ops::Sub,
-};
+use std::cell::OnceCell;
 
 use ahash::{AHashMap, AHashSet};
-use lazy_static::lazy_static;
 
-const INPUT: &str = include_str!("../problems/problem19");
+use crate::{
+    error::{end_of, parse_number},
+    solution::{Answer, Solution},
+    util::{Rotation, Vec3},
+};
 
 // This is a re-implementation of the python version.
 // See the Python version for more detailed comments.
 
-#[derive(PartialEq, Eq, Hash, Clone, Debug, Copy)]
-struct Point(i32, i32, i32);
-
-impl Point {
-    fn l1(&self) -> u32 {
-        let &Point(p1, p2, p3) = self;
-        p1.unsigned_abs() + p2.unsigned_abs() + p3.unsigned_abs()
-    }
-
-    fn component(&self, i: usize) -> i32 {
-        match i {
-            0 => self.0,
-            1 => self.1,
-            2 => self.2,
-            _ => panic!("out of bounds"),
-        }
-    }
-
-    fn rotate(&self, (s1, s2, s3, (i, j, k)): Rotation) -> Point {
-        Point(
-            s1 * self.component(i),
-            s2 * self.component(j),
-            s3 * self.component(k),
-        )
-    }
-}
-
-impl Sub for Point {
-    type Output = Point;
-
-    fn sub(self, Point(b1, b2, b3): Self) -> Self::Output {
-        let Point(a1, a2, a3) = self;
-        Point(a1 - b1, a2 - b2, a3 - b3)
-    }
-}
-
-type Permutation = (usize, usize, usize);
-type Rotation = (i32, i32, i32, Permutation);
-
-const S3_WITH_SIGNS: [(i32, Permutation); 6] = [
-    (1, (0, 1, 2)),
-    (1, (1, 2, 0)),
-    (1, (2, 0, 1)),
-    (-1, (1, 0, 2)),
-    (-1, (0, 2, 1)),
-    (-1, (2, 1, 0)),
-];
-
-lazy_static! {
-    static ref ROTATIONS: Vec<Rotation> = {
-        let mut ret = Vec::new();
-
-        for &(s, perm) in &S3_WITH_SIGNS {
-            for s1 in [-1, 1] {
-                for s2 in [-1, 1] {
-                    for s3 in [-1, 1] {
-                        if s * s1 * s2 * s3 == -1 {
-                            continue;
-                        }
-
-                        ret.push((s1, s2, s3, perm))
-                    }
-                }
-            }
-        }
-
-        ret
-    };
-}
+type Point = Vec3<i32>;
 
+#[derive(Clone)]
 struct BeaconSet {
-    points_with_distances: AHashMap<Point, AHashSet<u32>>,
+    points_with_distances: AHashMap<Point, AHashSet<i32>>,
 }
 
 impl BeaconSet {
     fn from(set: AHashSet<Point>) -> Self {
-        let mut points_with_distances: AHashMap<Point, AHashSet<u32>> = Default::default();
+        let mut points_with_distances: AHashMap<Point, AHashSet<i32>> = Default::default();
 
         for &x in &set {
             let x_distances = points_with_distances.entry(x).or_default();
 
             for &y in &set {
-                x_distances.insert((x - y).l1());
+                x_distances.insert((x - y).manhattan());
             }
         }
 
@@ -107,7 +41,7 @@ impl BeaconSet {
         let old = std::mem::replace(&mut self.points_with_distances, AHashMap::with_capacity(n));
 
         for (x, distances) in old {
-            let new_x = x.rotate(rot) - d;
+            let new_x = rot * x - d;
             self.points_with_distances.insert(new_x, distances);
         }
     }
@@ -120,12 +54,12 @@ fn find_rotation_and_offset(set1: &BeaconSet, set2: &BeaconSet) -> Option<(Rotat
                 continue;
             }
 
-            for &rot in ROTATIONS.iter() {
-                let d = q.rotate(rot) - p;
+            for rot in Rotation::all() {
+                let d = rot * q - p;
 
                 let mut m = 0;
-                for (r, _) in &set2.points_with_distances {
-                    let t_and_r = r.rotate(rot)  - d;
+                for &r in set2.points_with_distances.keys() {
+                    let t_and_r = rot * r - d;
                     if set1.points_with_distances.contains_key(&t_and_r) {
                         m += 1;
                     }
@@ -141,55 +75,38 @@ fn find_rotation_and_offset(set1: &BeaconSet, set2: &BeaconSet) -> Option<(Rotat
     None
 }
 
-pub fn solve() -> crate::Result<()> {
-    let lines = INPUT.lines().chain(std::iter::once("".into()));
-
-    // Assume that the scanners are numbered 0,1,2,3... (without any holes)
-    let mut scanners: Vec<BeaconSet> = Default::default();
-
-    let mut current = None;
-    let mut point_set: AHashSet<Point> = Default::default();
-
-    for line in lines {
-        let line = line.trim();
-
-        if line.starts_with("---") {
-            let mut split = line.split_ascii_whitespace();
-
-            let nr = split.nth(2).ok_or(crate::Error::InvalidInput)?;
-            current = Some(nr.parse::<usize>()?);
-        } else if line.is_empty() {
-            scanners.insert(
-                current.ok_or(crate::Error::InvalidInput)?,
-                BeaconSet::from(point_set),
-            );
-            point_set = Default::default();
-        } else {
-            let mut split = line.split(',');
-
-            let x1 = split
-                .next()
-                .ok_or(crate::Error::InvalidInput)?
-                .parse::<i32>()?;
-            let x2 = split
-                .next()
-                .ok_or(crate::Error::InvalidInput)?
-                .parse::<i32>()?;
-            let x3 = split
-                .next()
-                .ok_or(crate::Error::InvalidInput)?
-                .parse::<i32>()?;
-
-            point_set.insert(Point(x1, x2, x3));
-        }
+struct Alignment {
+    beacons: AHashSet<Point>,
+    scanner_offsets: AHashMap<usize, Point>,
+}
+
+pub struct Scanners {
+    scanners: Vec<BeaconSet>,
+    // Both parts need the scanners to be aligned, which is by far the most expensive step,
+    // so it is only done once. None if the scanners cannot be aligned.
+    alignment: OnceCell<Option<Alignment>>,
+}
+
+impl Scanners {
+    fn alignment(&self) -> crate::Result<&Alignment> {
+        self.alignment
+            .get_or_init(|| align(self.scanners.clone()))
+            .as_ref()
+            .ok_or(crate::Error::NoSolution)
     }
+}
 
+/// Align all scanners to the first one, or None if some scanner does not overlap with any of
+/// the others
+fn align(mut scanners: Vec<BeaconSet>) -> Option<Alignment> {
     let n = scanners.len();
 
     let mut scanner_offsets: AHashMap<usize, Point> = Default::default();
-    scanner_offsets.insert(0, Point(0, 0, 0));
+    scanner_offsets.insert(0, Point::ZERO);
 
     while scanner_offsets.len() < scanners.len() {
+        let aligned_before = scanner_offsets.len();
+
         for t in 0..n {
             if scanner_offsets.contains_key(&t) {
                 continue;
@@ -204,6 +121,10 @@ pub fn solve() -> crate::Result<()> {
                 }
             }
         }
+
+        if scanner_offsets.len() == aligned_before {
+            return None;
+        }
     }
 
     // Assemble list of all beacons
@@ -214,23 +135,93 @@ pub fn solve() -> crate::Result<()> {
         }
     }
 
-    println!("Problem 1: {}", all_beacons.len());
+    Some(Alignment {
+        beacons: all_beacons,
+        scanner_offsets,
+    })
+}
 
-    let mut max_distance = 0;
-    for s in 0..n {
-        for t in 0..n {
-            if s == t {
-                continue;
-            }
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Scanners;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let lines = input.lines().chain(std::iter::once(""));
+
+        // Assume that the scanners are numbered 0,1,2,3... (without any holes)
+        let mut scanners: Vec<BeaconSet> = Default::default();
+
+        let mut current = None;
+        let mut point_set: AHashSet<Point> = Default::default();
+
+        for line in lines {
+            let line = line.trim();
+
+            if line.starts_with("---") {
+                let mut split = line.split_ascii_whitespace();
+
+                let nr = split
+                    .nth(2)
+                    .ok_or_else(|| crate::Error::parse(input, end_of(line), "--- scanner N ---"))?;
 
-            max_distance = std::cmp::max(
-                (scanner_offsets[&s] - scanner_offsets[&t]).l1(),
-                max_distance,
-            )
+                if parse_number::<usize>(input, nr)? != scanners.len() {
+                    let expected = format!("scanner {}", scanners.len());
+                    return Err(crate::Error::parse(input, nr, expected));
+                }
+
+                current = Some(scanners.len());
+            } else if line.is_empty() {
+                if current.take().is_some() {
+                    scanners.push(BeaconSet::from(std::mem::take(&mut point_set)));
+                }
+            } else if current.is_none() {
+                return Err(crate::Error::parse(input, line, "--- scanner N ---"));
+            } else {
+                let mut split = line.split(',');
+                let mut next = || {
+                    let nr = split
+                        .next()
+                        .ok_or_else(|| crate::Error::parse(input, end_of(line), "a point x,y,z"))?;
+                    parse_number::<i32>(input, nr)
+                };
+
+                let x1 = next()?;
+                let x2 = next()?;
+                let x3 = next()?;
+
+                point_set.insert(Vec3::new(x1, x2, x3));
+            }
         }
+
+        Ok(Scanners {
+            scanners,
+            alignment: OnceCell::new(),
+        })
+    }
+
+    fn part1(scanners: &Self::Input) -> crate::Result<Answer> {
+        Ok(scanners.alignment()?.beacons.len().into())
     }
 
-    println!("Problem 2: {}", max_distance);
+    fn part2(scanners: &Self::Input) -> crate::Result<Answer> {
+        let scanner_offsets = &scanners.alignment()?.scanner_offsets;
+        let n = scanner_offsets.len();
 
-    Ok(())
+        let mut max_distance = 0;
+        for s in 0..n {
+            for t in 0..n {
+                if s == t {
+                    continue;
+                }
+
+                max_distance = std::cmp::max(
+                    (scanner_offsets[&s] - scanner_offsets[&t]).manhattan(),
+                    max_distance,
+                )
+            }
+        }
+
+        Ok(max_distance.into())
+    }
 }
@@ -1,9 +1,11 @@
-use crate::Error;
-
-const INPUT: &str = include_str!("../problems/problem2");
+use crate::{
+    error::{end_of, parse_number},
+    solution::{Answer, Solution},
+    Error,
+};
 
 #[derive(Debug, Clone, Copy)]
-enum Instruction {
+pub enum Instruction {
     Forward(i64),
     Down(i64),
     Up(i64),
@@ -14,57 +16,72 @@ impl TryFrom<&str> for Instruction {
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let mut split = value.split_ascii_whitespace();
-        let first = split.next().ok_or(Error::InvalidInput)?;
-        let second = split.next().ok_or(Error::InvalidInput)?;
+        let missing = || Error::parse(value, end_of(value), "a direction and a distance");
+
+        let first = split.next().ok_or_else(missing)?;
+        let second = split.next().ok_or_else(missing)?;
 
-        let nr = second.parse()?;
+        let nr = parse_number(value, second)?;
 
         match first {
             "forward" => Ok(Instruction::Forward(nr)),
             "down" => Ok(Instruction::Down(nr)),
             "up" => Ok(Instruction::Up(nr)),
-            _ => Err(Error::InvalidInput),
+            _ => Err(Error::parse(value, first, "forward, down or up")),
         }
     }
 }
 
-pub fn solve() -> Result<(), crate::Error> {
-    let instructions = INPUT
-        .lines()
-        .filter_map(|s| Instruction::try_from(s).ok())
-        .collect::<Vec<Instruction>>();
-
-    // Part 1
-    let mut pos = 0;
-    let mut depth = 0;
-
-    for &inst in &instructions {
-        match inst {
-            Instruction::Down(x) => depth += x,
-            Instruction::Up(x) => depth -= x,
-            Instruction::Forward(x) => pos += x,
-        }
-    }
+/// The result of checked arithmetic. Real inputs stay far away from overflowing, so an
+/// overflow means the input is invalid.
+fn checked(x: Option<i64>) -> crate::Result<i64> {
+    x.ok_or(Error::InvalidInput)
+}
+
+pub struct Day2;
 
-    println!("Problem 1: {}", pos * depth);
+impl Solution for Day2 {
+    type Input = Vec<Instruction>;
 
-    // Part 2
-    let mut pos = 0;
-    let mut depth = 0;
-    let mut aim = 0;
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, s)| Instruction::try_from(s).map_err(|e| e.on_line(i + 1)))
+            .collect()
+    }
 
-    for inst in instructions {
-        match inst {
-            Instruction::Down(x) => aim += x,
-            Instruction::Up(x) => aim -= x,
-            Instruction::Forward(x) => {
-                pos += x;
-                depth += aim * x;
+    fn part1(instructions: &Self::Input) -> crate::Result<Answer> {
+        let mut pos: i64 = 0;
+        let mut depth: i64 = 0;
+
+        for &inst in instructions {
+            match inst {
+                Instruction::Down(x) => depth = checked(depth.checked_add(x))?,
+                Instruction::Up(x) => depth = checked(depth.checked_sub(x))?,
+                Instruction::Forward(x) => pos = checked(pos.checked_add(x))?,
             }
         }
+
+        Ok(checked(pos.checked_mul(depth))?.into())
     }
 
-    println!("Problem 2: {}", pos * depth);
+    fn part2(instructions: &Self::Input) -> crate::Result<Answer> {
+        let mut pos: i64 = 0;
+        let mut depth: i64 = 0;
+        let mut aim: i64 = 0;
+
+        for &inst in instructions {
+            match inst {
+                Instruction::Down(x) => aim = checked(aim.checked_add(x))?,
+                Instruction::Up(x) => aim = checked(aim.checked_sub(x))?,
+                Instruction::Forward(x) => {
+                    pos = checked(pos.checked_add(x))?;
+                    depth = checked(aim.checked_mul(x).and_then(|d| depth.checked_add(d)))?;
+                }
+            }
+        }
 
-    Ok(())
+        Ok(checked(pos.checked_mul(depth))?.into())
+    }
 }
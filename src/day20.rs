@@ -1,42 +1,29 @@
-use std::{cmp::{max, min}, fmt::Display};
+use std::fmt::Display;
 
-use ahash::AHashSet;
-
-const INPUT: &str = include_str!("../problems/problem20");
+use crate::{
+    solution::{Answer, Solution},
+    util::{Grid, Vec2},
+};
 
+/// An infinite image: the pixels within the grid, and all pixels outside of it, which are
+/// either all lit or all dark
 #[derive(Debug, Clone)]
-struct Image {
-    pixels: AHashSet<(i16, i16)>,
-    x_min: i16,
-    x_max: i16,
-    y_min: i16,
-    y_max: i16,
+pub struct Image {
+    pixels: Grid<bool>,
     edge_lit: bool,
 }
 
 impl Display for Image {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in self.y_min..=self.y_max {
-            for x in self.y_min..=self.y_max {
-                if self.pixels.contains(&(y, x)) {
-                    write!(f, "#")?
-                } else {
-                    write!(f, ".")?
-                }
-            }
-            writeln!(f)?;
-        }
-
-        Ok(())
+        write!(f, "{}", self.pixels.display_with(|&lit| if lit { '#' } else { '.' }))
     }
 }
 
 impl Image {
-    fn is_lit(&self, x: i16, y: i16) -> bool {
-        if self.x_min <= x && x <= self.x_max && self.y_min <= y && y <= self.y_max {
-            self.pixels.contains(&(y, x))
-        } else {
-            self.edge_lit
+    fn is_lit(&self, p: Vec2<isize>) -> bool {
+        match self.pixels.position(p.x, p.y) {
+            Some(pos) => self.pixels[pos],
+            None => self.edge_lit,
         }
     }
 
@@ -48,133 +35,90 @@ impl Image {
             algorithm[0]
         };
 
-        // Try and reduce the size of the bounding box to save on iterations
-        let (mut y_min, mut y_max) = (std::i16::MAX, std::i16::MIN);
-        let (mut x_min, mut x_max) = (std::i16::MAX, std::i16::MIN);
-
-        let mut pixels: AHashSet<(i16, i16)> = Default::default();
-        for x in (self.x_min - 3)..=(self.x_max + 3) {
-            for y in (self.y_min - 3)..=(self.y_max + 3) {
-                let mut idx = 0;
-
-                // Note that the order here is very much relevant
-                for (dy, dx) in [
-                    (-1, -1),
-                    (-1, 0),
-                    (-1, 1),
-                    (0, -1),
-                    (0, 0),
-                    (0, 1),
-                    (1, -1),
-                    (1, 0),
-                    (1, 1),
-                ] {
-                    idx *= 2;
-                    idx += self.is_lit(x + dx, y + dy) as usize;
-                }
+        // Only the pixels next to the grid can change differently from the edge, so the grid
+        // grows by one pixel on each side. Pixel (x, y) of the new grid is pixel
+        // (x - 1, y - 1) of the old one.
+        let (width, height) = (self.pixels.width() + 2, self.pixels.height() + 2);
 
-                let lit = algorithm[idx];
-                if lit {
-                    pixels.insert((y, x));
-                }
+        let pixels = Grid::from_fn(width, height, |x, y| {
+            let p = Vec2::new(x as isize, y as isize) - Vec2::new(1, 1);
+            let mut idx = 0;
 
-                if lit != edge_lit {
-                    y_min = min(y_min, y);
-                    y_max = max(y_max, y);
-                    x_min = min(x_min, x);
-                    x_max = max(x_max, x);
+            // Note that the order here is very much relevant: row by row, from the top left
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    idx *= 2;
+                    idx += self.is_lit(p + Vec2::new(dx, dy)) as usize;
                 }
             }
-        }
-
-        // Degenerate case, that will never actually occur: It could happen that
-        // we never actually change the values of these variables.
-        if y_min == std::i16::MAX {
-            y_min = self.y_min;
-            y_max = self.y_min;
-            x_min = self.x_min;
-            x_max = self.x_min;
-        }
 
+            algorithm[idx]
+        });
 
-        Self {
-            pixels,
-            y_min,
-            y_max,
-            x_min,
-            x_max,
-            edge_lit,
-        }
+        Self { pixels, edge_lit }
     }
 
     fn count_pixels(&self) -> Option<usize> {
         if !self.edge_lit {
-            Some(self.pixels.len())
+            Some(self.pixels.iter().filter(|&&lit| lit).count())
         } else {
             None
         }
     }
 }
 
-fn parse_image<S: AsRef<str>, I: IntoIterator<Item = S>>(lines: I) -> Image {
-    let mut pixels: AHashSet<(i16, i16)> = Default::default();
+pub struct Day20;
 
-    let mut max_x = 0;
-    let mut max_y = 0;
+impl Solution for Day20 {
+    type Input = (Vec<bool>, Image);
 
-    for (y, line) in lines.into_iter().enumerate() {
-        let line = line.as_ref();
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let mut lines = input.lines();
 
-        for (x, c) in line.trim().chars().enumerate() {
-            if c == '#' {
-                pixels.insert((y as i16, x as i16));
-            }
+        let line = lines.next().ok_or(crate::Error::NoInput)?;
 
-            max_x = max(max_x, x);
-        }
+        let algorithm = line
+            .char_indices()
+            .map(|(i, x)| match x {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                _ => Err(crate::Error::parse(input, &line[i..i + x.len_utf8()], "# or .")),
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
 
-        max_y = max(max_y, y);
-    }
-
-    Image {
-        pixels,
-        x_min: 0,
-        x_max: max_x as i16,
-        y_min: 0,
-        y_max: max_y as i16,
-        edge_lit: false,
-    }
-}
+        if algorithm.len() != 512 {
+            return Err(crate::Error::parse(input, line, "512 characters"));
+        }
 
-pub fn solve() -> crate::Result<()> {
-    let mut lines = INPUT.lines();
+        // Skip empty line
+        let _ = lines.next();
 
-    let algorithm = lines
-        .next()
-        .ok_or(crate::Error::NoInput)?
-        .chars()
-        .map(|x| x == '#')
-        .collect::<Vec<_>>();
+        let pixels = Grid::parse_lines(input, lines, "# or .", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
 
-    // Skip empty line
-    let _ = lines.next();
+        let img = Image {
+            pixels,
+            edge_lit: false,
+        };
 
-    let img = parse_image(lines);
+        Ok((algorithm, img))
+    }
 
-    println!(
-        "Problem 1: {}",
-        img.enhance(&algorithm)
-            .enhance(&algorithm)
-            .count_pixels()
-            .unwrap()
-    );
+    fn part1((algorithm, img): &Self::Input) -> crate::Result<Answer> {
+        let enhanced = img.enhance(algorithm).enhance(algorithm);
 
-    let mut enhanced = img;
-    for _ in 0..50 {
-        enhanced = enhanced.enhance(&algorithm);
+        Ok(enhanced.count_pixels().ok_or(crate::Error::NoSolution)?.into())
     }
 
-    println!("Problem 2: {}", enhanced.count_pixels().unwrap());
+    fn part2((algorithm, img): &Self::Input) -> crate::Result<Answer> {
+        let mut enhanced = img.clone();
+        for _ in 0..50 {
+            enhanced = enhanced.enhance(algorithm);
+        }
 
-    Ok(())
+        Ok(enhanced.count_pixels().ok_or(crate::Error::NoSolution)?.into())
+    }
 }
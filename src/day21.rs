@@ -1,6 +1,9 @@
-use std::collections::HashMap;
-
-const INPUT: &str = include_str!("../problems/problem21");
+use crate::{
+    error::{end_of, parse_number},
+    solution::{Answer, Solution},
+    solvers::Part,
+    util::{Memo, Table},
+};
 
 fn solve_problem1(mut p: [u64; 2]) -> u64 {
     let mut score = [0, 0];
@@ -30,15 +33,25 @@ fn solve_problem1(mut p: [u64; 2]) -> u64 {
     (rounds * 3) * (score[(turn + 1) % 2])
 }
 
-fn simulate_quantum(
-    p: [u8; 2],
-    score: [u8; 2],
-    turn: usize,
-    cache: &mut HashMap<([u8; 2], [u8; 2], u8), [u64; 2]>,
-) -> [u64; 2] {
-    if let Some(&ret) = cache.get(&(p, score, turn as u8)) {
-        return ret;
-    }
+/// The positions and scores of both players and whose turn it is
+type State = ([u8; 2], [u8; 2], u8);
+
+type QuantumMemo = Memo<State, [u64; 2], Table<State, [u64; 2]>>;
+
+/// A memo with a slot for every state before the game is won
+fn quantum_memo() -> QuantumMemo {
+    // Positions are 1 to 10 and scores below 21
+    Memo::table(10 * 10 * 21 * 21 * 2, |&(p, score, turn)| {
+        let positions = (p[0] as usize - 1) * 10 + (p[1] as usize - 1);
+        let scores = score[0] as usize * 21 + score[1] as usize;
+
+        (positions * 21 * 21 + scores) * 2 + turn as usize
+    })
+}
+
+/// The number of universes in which each player wins
+fn simulate_quantum(memo: &mut QuantumMemo, (p, score, turn): State) -> [u64; 2] {
+    let turn = turn as usize;
 
     let mut ret = [0, 0];
     for r1 in 1..=3 {
@@ -46,8 +59,8 @@ fn simulate_quantum(
             for r3 in 1..=3 {
                 let sum = r1 + r2 + r3;
 
-                let mut p = p.clone();
-                let mut score = score.clone();
+                let mut p = p;
+                let mut score = score;
 
                 let new_space = (p[turn] + sum - 1) % 10 + 1;
 
@@ -57,7 +70,7 @@ fn simulate_quantum(
                 if score[turn] >= 21 {
                     ret[turn] += 1;
                 } else {
-                    let s = simulate_quantum(p, score, (turn + 1) % 2, cache);
+                    let s = memo.call((p, score, (turn as u8 + 1) % 2), &simulate_quantum);
 
                     ret[0] += s[0];
                     ret[1] += s[1];
@@ -66,33 +79,56 @@ fn simulate_quantum(
         }
     }
 
-    cache.insert((p, score, turn as u8), ret);
     ret
 }
 
-pub fn solve() -> crate::Result<()> {
-    let mut lines = INPUT.lines();
-
-    let mut p = [0, 0];
-    for i in 0..=1 {
-        p[i] = lines
-        .next()
-        .ok_or(crate::Error::InvalidInput)?
-        .split(": ")
-        .nth(1)
-        .ok_or(crate::Error::InvalidInput)?
-        .parse::<u64>()?;
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = [u64; 2];
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let mut lines = input.lines();
+
+        let mut p = [0, 0];
+        for x in p.iter_mut() {
+            let line = lines.next().ok_or_else(|| {
+                crate::Error::parse(input, end_of(input), "Player N starting position: M")
+            })?;
+            let (_, position) = line.split_once(": ").ok_or_else(|| {
+                crate::Error::parse(input, line, "Player N starting position: M")
+            })?;
+
+            *x = match parse_number::<u64>(input, position)? {
+                p @ 1..=10 => p,
+                _ => return Err(crate::Error::parse(input, position, "a position from 1 to 10")),
+            };
+        }
+
+        Ok(p)
+    }
+
+    fn part1(&p: &Self::Input) -> crate::Result<Answer> {
+        Ok(solve_problem1(p).into())
     }
 
-    println!("Problem 1: {}", solve_problem1(p));
+    fn part2(&p: &Self::Input) -> crate::Result<Answer> {
+        let wins = quantum_memo().call(([p[0] as u8, p[1] as u8], [0, 0], 0), &simulate_quantum);
 
-    println!(
-        "Problem 2: {:?}",
-        simulate_quantum([p[0] as u8, p[1] as u8], [0, 0], 0, &mut Default::default())
-            .iter()
-            .max()
-            .unwrap()
-    );
+        Ok(std::cmp::max(wins[0], wins[1]).into())
+    }
+
+    fn explain(&p: &Self::Input, part: Part) -> crate::Result<Vec<String>> {
+        if part == Part::One {
+            return Ok(Vec::new());
+        }
 
-    Ok(())
+        let mut memo = quantum_memo();
+        let wins = memo.call(([p[0] as u8, p[1] as u8], [0, 0], 0), &simulate_quantum);
+
+        Ok(vec![
+            format!("Player 1 wins in {} universes, player 2 in {}", wins[0], wins[1]),
+            format!("{} game states, cache: {}", memo.len(), memo.stats()),
+        ])
+    }
 }
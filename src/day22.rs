@@ -1,41 +1,52 @@
 use std::cmp::{max, min};
 
-const INPUT: &str = include_str!("../problems/problem22");
+use crate::{
+    error::Parsed,
+    solution::{Answer, Solution},
+    util::parse::{integer, literal, map, one_of, pair, parse_all, preceded, terminated, Parser},
+};
 
-fn interval_intersection((a, b): (i32, i32), (u, v): (i32, i32)) -> (i32, i32) {
+/// The coordinates of the cubes. The input only has `i32` coordinates, the wider type leaves
+/// room to step past them.
+pub type T = i64;
+
+fn interval_intersection((a, b): (T, T), (u, v): (T, T)) -> (T, T) {
     (max(a, u), min(b, v))
 }
 
-fn interval_difference((a, b): (i32, i32), (u, v): (i32, i32)) -> [(i32, i32); 2] {
+fn interval_difference((a, b): (T, T), (u, v): (T, T)) -> [(T, T); 2] {
     [(a, min(b, u - 1)), (max(a, v + 1), b)]
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Rectangle<const N: usize> {
-    ranges: [(i32, i32); N],
+pub struct Rectangle<const N: usize> {
+    pub ranges: [(T, T); N],
 }
 
 impl<const N: usize> Rectangle<N> {
-    fn is_empty(&self) -> bool {
+    pub fn is_empty(&self) -> bool {
         self.ranges.iter().any(|(a, b)| a > b)
     }
 
-    fn count(&self) -> usize {
+    /// The number of cubes inside the rectangle. Fails if it does not fit into a u64.
+    pub fn count(&self) -> crate::Result<u64> {
         if self.is_empty() {
-            return 0;
+            return Ok(0);
         }
 
-        let mut res = 1;
+        let mut res: u64 = 1;
 
         for i in 0..N {
             let (a, b) = self.ranges[i];
-            res *= (b - a + 1) as usize;
+            res = res
+                .checked_mul((b - a + 1) as u64)
+                .ok_or(crate::Error::InvalidInput)?;
         }
 
-        res
+        Ok(res)
     }
 
-    fn intersect(&self, other: &Rectangle<N>) -> Rectangle<N> {
+    pub fn intersect(&self, other: &Rectangle<N>) -> Rectangle<N> {
         let mut ret = Rectangle {
             ranges: [(0, 0); N],
         };
@@ -109,9 +120,9 @@ fn rectangle_difference_3(p: Rectangle<3>, q: Rectangle<3>) -> [Rectangle<3>; 6]
 }
 
 #[derive(Debug, Clone, Copy)]
-struct Instruction {
-    rect: Rectangle<3>,
-    on: bool,
+pub struct Instruction {
+    pub rect: Rectangle<3>,
+    pub on: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -121,19 +132,21 @@ struct Node {
 }
 
 impl Node {
-    fn count(&self, val: bool) -> usize {
+    fn count(&self, val: bool) -> crate::Result<u64> {
         match &self.value {
             NodeValue::Leaf(b) if *b == val => self.rect.count(),
             NodeValue::Children(children) => {
-                let mut ret = 0;
+                let mut ret: u64 = 0;
 
                 for child in children {
-                    ret += child.count(val);
+                    ret = ret
+                        .checked_add(child.count(val)?)
+                        .ok_or(crate::Error::InvalidInput)?;
                 }
 
-                ret
+                Ok(ret)
             }
-            _ => 0,
+            _ => Ok(0),
         }
     }
 }
@@ -220,68 +233,79 @@ fn set(root: &mut Node, target: Rectangle<3>, val: bool) {
     set_inner(root, target, val)
 }
 
-fn parse_range(s: &str) -> Option<(i32, i32)> {
-    let mut split = s.split("..");
+/// A range like "x=10..12", where the prefix is "x="
+fn range<'a>(prefix: &'static str) -> impl Parser<'a, (T, T)> {
+    let bound = || map(integer::<i32>(), T::from);
 
-    let a = split.next()?.parse::<i32>().ok()?;
-    let b = split.next()?.parse::<i32>().ok()?;
-
-    Some((a, b))
+    preceded(
+        literal(prefix),
+        pair(terminated(bound(), literal("..")), bound()),
+    )
 }
 
-fn parse_instruction(mut s: &str) -> Option<Instruction> {
-    let on = if s.starts_with("on ") {
-        s = &s[3..];
-        true
-    } else if s.starts_with("off ") {
-        s = &s[4..];
-        false
-    } else {
-        return None;
-    };
+/// An instruction like "on x=10..12,y=10..12,z=10..12"
+fn instruction(s: &str) -> Parsed<'_, Instruction> {
+    let on = one_of(&[("on ", true), ("off ", false)], "on or off");
+    let ranges = pair(
+        terminated(range("x="), literal(",")),
+        pair(terminated(range("y="), literal(",")), range("z=")),
+    );
 
-    let mut ranges = s.split(',');
-
-    let xs = parse_range(&ranges.next()?[2..])?;
-    let ys = parse_range(&ranges.next()?[2..])?;
-    let zs = parse_range(&ranges.next()?[2..])?;
+    map(pair(on, ranges), |(on, (xs, (ys, zs)))| Instruction {
+        rect: Rectangle {
+            ranges: [xs, ys, zs],
+        },
+        on,
+    })(s)
+}
 
-    let rect = Rectangle {
-        ranges: [xs, ys, zs],
+/// Run all instructions within the given region and count the cubes that are on afterwards.
+/// Fails if there are more of them than fit into a u64.
+pub fn reboot(instructions: &[Instruction], region: Rectangle<3>) -> crate::Result<u64> {
+    let mut root = Node {
+        rect: region,
+        value: NodeValue::Leaf(false),
     };
 
-    Some(Instruction { rect, on })
+    for &Instruction { rect, on } in instructions {
+        set(&mut root, rect, on);
+    }
+
+    root.count(true)
 }
 
-pub fn solve() -> crate::Result<()> {
-    let instructions = INPUT.lines()
-        .map(|line| parse_instruction(&line))
-        .collect::<Option<Vec<Instruction>>>()
-        .ok_or(crate::Error::InvalidInput)?;
+pub struct Day22;
 
-    let init_rect = Rectangle {
-        ranges: [(-50, 50); 3],
-    };
+impl Solution for Day22 {
+    type Input = Vec<Instruction>;
 
-    let mut root1 = Node {
-        rect: init_rect.clone(),
-        value: NodeValue::Leaf(false),
-    };
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        input
+            .lines()
+            .map(|line| parse_all(input, line, instruction))
+            .collect()
+    }
 
-    let mut root2 = Node {
-        rect: Rectangle {
-            ranges: [(-100_000, 100_000); 3],
-        },
-        value: NodeValue::Leaf(false),
-    };
+    fn part1(instructions: &Self::Input) -> crate::Result<Answer> {
+        let init_rect = Rectangle {
+            ranges: [(-50, 50); 3],
+        };
 
-    for Instruction { rect, on } in instructions {
-        set(&mut root1, rect, on);
-        set(&mut root2, rect, on);
+        Ok(reboot(instructions, init_rect)?.into())
     }
 
-    println!("Problem 1: {}", root1.count(true));
-    println!("Problem 2: {}", root2.count(true));
+    fn part2(instructions: &Self::Input) -> crate::Result<Answer> {
+        // The whole reactor, i.e. the smallest region that contains all cuboids
+        let mut rect = Rectangle {
+            ranges: [(T::MAX, T::MIN); 3],
+        };
+
+        for instruction in instructions {
+            for (range, &(a, b)) in rect.ranges.iter_mut().zip(&instruction.rect.ranges) {
+                *range = (min(range.0, a), max(range.1, b));
+            }
+        }
 
-    Ok(())
+        Ok(reboot(instructions, rect)?.into())
+    }
 }
@@ -1,14 +1,14 @@
-use std::{collections::VecDeque, fmt::Display};
+use std::fmt::Display;
 
 use ahash::AHashMap;
 
 use crate::{
-    util::{Edge, Graph},
+    error::end_of,
+    solution::{Answer, Solution},
+    solvers::Part,
+    util::{Edge, Graph, Path, UnweightedGraph},
 };
 
-const INPUT: &str = include_str!("../problems/problem23");
-const INF: u64 = 1 << 42;
-
 // We encode A as 1, B as 2, C as 3 and D as 4. Further, we index the rooms by the same
 // number, i.e. room 1 is actually indexed by 1 (and not by zero!)
 
@@ -21,7 +21,7 @@ enum Position {
 struct StateGraph;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
-struct Board<const N: usize> {
+pub struct Board<const N: usize> {
     hallway: [u8; 11],
     rooms: [[u8; N]; 4],
 }
@@ -39,13 +39,13 @@ impl<const N: usize> Display for Board<N> {
             }
         }
 
-        write!(f, "#############\n")?;
+        writeln!(f, "#############")?;
 
         write!(f, "#")?;
         for i in 0..11 {
             write!(f, "{}", letter(self.get(Position::Hallway(i))))?;
         }
-        write!(f, "#\n")?;
+        writeln!(f, "#")?;
 
         for j in 0..N as u8 {
             if j == 0 {
@@ -60,9 +60,9 @@ impl<const N: usize> Display for Board<N> {
             }
 
             if j == 0 {
-                write!(f, "##\n")?;
+                writeln!(f, "##")?;
             } else {
-                write!(f, "\n")?;
+                writeln!(f)?;
             }
         }
 
@@ -70,6 +70,28 @@ impl<const N: usize> Display for Board<N> {
     }
 }
 
+// Amphipods can walk to adjacent positions that are not occupied
+impl<const N: usize> UnweightedGraph<Position> for Board<N> {
+    fn adjacent(&self, &pos: &Position, out: &mut Vec<Position>) {
+        let from = out.len();
+        self.adjacent_positions(pos, out);
+
+        let mut i = from;
+        while i < out.len() {
+            if self.get(out[i]) > 0 {
+                out.swap_remove(i);
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
+
+/// The energy it takes to move amphipod x by one step
+fn cost(x: u8) -> u64 {
+    10u64.pow(x as u32 - 1)
+}
+
 impl<const N: usize> Graph<Board<N>> for StateGraph {
     fn neighbors(&self, v: &Board<N>) -> Vec<crate::util::Edge<Board<N>>> {
         fn room_state<const N: usize>(board: &Board<N>, i: u8) -> (bool, Option<usize>) {
@@ -87,10 +109,6 @@ impl<const N: usize> Graph<Board<N>> for StateGraph {
             (good, empty_spot)
         }
 
-        fn cost(x: u8) -> u64 {
-            10u64.pow(x as u32 - 1)
-        }
-
         fn next_states_from_hallway<const N: usize>(
             board: &Board<N>,
             out: &mut Vec<Edge<Board<N>>>,
@@ -148,11 +166,9 @@ impl<const N: usize> Graph<Board<N>> for StateGraph {
                     if let Some(dist) = distances.get(&to) {
                         let new_board = board.make_move_unchecked(from, to);
 
-                        // Make sure the "interior" of the hallway stays sorted,
-                        // otherwise two amphipods will just block each other there forever
-                        // (since they have to get past each other to move into their rooms)
-                        let (a, b, c) = (new_board.hallway[3], new_board.hallway[5], new_board.hallway[7]);
-                        if (a == 0 || b == 0 || a < b) && (b == 0 || c == 0 || b < c) {
+                        // Two amphipods that have to get past each other to move into
+                        // their rooms will just block each other there forever
+                        if !new_board.is_deadlocked() {
                             out.push(Edge {
                                 vertex: new_board,
                                 cost: dist * cost(x),
@@ -182,6 +198,18 @@ impl<const N: usize> Board<N> {
         rooms: [[1; N], [2; N], [3; N], [4; N]],
     };
 
+    /// Whether two amphipods in the hallway both have to pass the other one to reach their
+    /// rooms. Room i lies below hallway position 2 * i.
+    fn is_deadlocked(&self) -> bool {
+        let occupied = || (0..11u8).filter(|&p| self.hallway[p as usize] > 0);
+
+        occupied().any(|p| {
+            occupied().any(|q| {
+                p < q && 2 * self.hallway[p as usize] > q && 2 * self.hallway[q as usize] < p
+            })
+        })
+    }
+
     fn get(&self, pos: Position) -> u8 {
         match pos {
             Position::Hallway(i) => self.hallway[i as usize],
@@ -197,14 +225,14 @@ impl<const N: usize> Board<N> {
     }
 
     fn make_move_unchecked(&self, from: Position, to: Position) -> Self {
-        let mut new = self.clone();
+        let mut new = *self;
         *new.get_mut(to) = new.get(from);
         *new.get_mut(from) = 0;
 
         new
     }
 
-    fn adjacent(&self, pos: Position, out: &mut Vec<Position>) {
+    fn adjacent_positions(&self, pos: Position, out: &mut Vec<Position>) {
         match pos {
             Position::Hallway(i) => {
                 if i + 1 < 11 {
@@ -243,90 +271,158 @@ impl<const N: usize> Board<N> {
     }
 
     fn distances(&self, start: Position) -> AHashMap<Position, u64> {
-        let mut queue = VecDeque::new();
-        queue.push_back(start);
+        self.bfs(start)
+    }
 
-        let mut visited: AHashMap<_, _> = Default::default();
-        visited.insert(start, 0);
+    fn distance(&self, start: Position, end: Position) -> Option<u64> {
+        self.distances(start).get(&end).copied()
+    }
+}
 
-        let mut adj = Vec::new();
+/// A lower bound for the energy needed to organize the amphipods: every amphipod that is not
+/// yet in its final spot has to walk at least to the entrance of its room and one step into it,
+/// ignoring any amphipods in the way
+fn min_energy<const N: usize>(b: &Board<N>) -> u64 {
+    let mut energy = 0;
 
-        while let Some(v) = queue.pop_front() {
-            adj.clear();
-            self.adjacent(v, &mut adj);
+    for (i, &x) in b.hallway.iter().enumerate() {
+        if x > 0 {
+            energy += (i.abs_diff(2 * x as usize) as u64 + 1) * cost(x);
+        }
+    }
 
-            for &w in &adj {
-                if visited.contains_key(&w) {
-                    continue;
-                }
+    for (r, room) in (1..=4u8).zip(&b.rooms) {
+        for (j, &x) in room.iter().enumerate() {
+            if x == 0 {
+                continue;
+            }
 
-                if self.get(w) > 0 {
-                    // Can't go to occupied field
-                    continue;
-                }
+            let steps_out = j as u64 + 1;
 
-                queue.push_back(w);
-                visited.insert(w, visited[&v] + 1);
+            if x != r {
+                let steps_over = (2 * r).abs_diff(2 * x) as u64;
+                energy += (steps_out + steps_over + 1) * cost(x);
+            } else if room[j + 1..].iter().any(|&y| y != r) {
+                // It has to make way for the amphipods below, so it leaves the room, steps
+                // aside, and comes back
+                energy += (steps_out + 3) * cost(x);
             }
         }
-
-        visited
     }
 
-    fn distance(&self, start: Position, end: Position) -> Option<u64> {
-        self.distances(start).get(&end).copied()
-    }
+    energy
 }
 
-fn is_solved<const N: usize>(b: &Board<N>) -> bool {
+pub fn is_solved<const N: usize>(b: &Board<N>) -> bool {
     b == &Board::SOLVED
 }
 
-fn find_solution<const N: usize>(b: Board<N>) -> u64 {
-    let (dist, _) = StateGraph.dijsktra(b, Some(Board::SOLVED), false);
-    dist[&Board::SOLVED]
+/// The least energy needed to organize the amphipods
+pub fn find_solution<const N: usize>(b: Board<N>) -> crate::Result<u64> {
+    let (dist, _) = StateGraph.a_star(b, Some(Board::SOLVED), Some(&min_energy), false);
+
+    dist.get(&Board::SOLVED).copied().ok_or(crate::Error::NoSolution)
 }
 
-pub fn solve() -> crate::Result<()> {
-    let mut rooms = [[0, 0], [0, 0], [0, 0], [0, 0]];
-
-    for (j, line) in INPUT.lines().skip(2).take(2).enumerate() {
-        for (i, x) in line
-            .trim()
-            .split('#')
-            .filter(|x| !x.trim().is_empty())
-            .enumerate()
-        {
-            let x = match x {
-                "A" => 1,
-                "B" => 2,
-                "C" => 3,
-                "D" => 4,
-                _ => panic!("unknown: {}", x),
-            };
-
-            rooms[i][j] = x;
+/// A cheapest sequence of moves that organizes the amphipods, as the boards after every move
+pub fn find_moves<const N: usize>(b: Board<N>) -> crate::Result<Path<Board<N>>> {
+    StateGraph
+        .shortest_path(b, Board::SOLVED, Some(&min_energy))
+        .ok_or(crate::Error::NoSolution)
+}
+
+/// Draw the board before and after each move, together with the energy the moves take
+pub fn render_moves<const N: usize>(path: &Path<Board<N>>) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut total = 0;
+
+    for (i, board) in path.vertices.iter().enumerate() {
+        if i == 0 {
+            lines.push("Start".to_owned());
+        } else {
+            let energy = path.costs[i - 1];
+            total += energy;
+
+            lines.push(String::new());
+            lines.push(format!("Move {}: {} energy ({} total)", i, energy, total));
         }
-    }
 
-    let board1 = Board {
-        hallway: [0; 11],
-        rooms,
-    };
+        lines.extend(board.to_string().lines().map(str::to_owned));
+    }
 
-    println!("Problem 1: {}", find_solution(board1));
+    lines
+}
 
-    let board2 = Board {
-        hallway: [0; 11],
+/// The board of the second part, with the two rows from the folded up part of the diagram
+/// inserted between the two rows of the input
+pub fn unfold(board: &Board<2>) -> Board<4> {
+    Board {
+        hallway: board.hallway,
         rooms: [
-            [board1.rooms[0][0], 4, 4, board1.rooms[0][1]],
-            [board1.rooms[1][0], 3, 2, board1.rooms[1][1]],
-            [board1.rooms[2][0], 2, 1, board1.rooms[2][1]],
-            [board1.rooms[3][0], 1, 3, board1.rooms[3][1]],
+            [board.rooms[0][0], 4, 4, board.rooms[0][1]],
+            [board.rooms[1][0], 3, 2, board.rooms[1][1]],
+            [board.rooms[2][0], 2, 1, board.rooms[2][1]],
+            [board.rooms[3][0], 1, 3, board.rooms[3][1]],
         ],
-    };
+    }
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Board<2>;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let mut rooms = [[0, 0], [0, 0], [0, 0], [0, 0]];
+        let mut lines = input.lines().skip(2);
+
+        for j in 0..2 {
+            let line = lines
+                .next()
+                .ok_or_else(|| crate::Error::parse(input, end_of(input), "a row of four rooms"))?;
+
+            let mut amphipods = line
+                .trim()
+                .split('#')
+                .filter(|x| !x.trim().is_empty());
+
+            for room in rooms.iter_mut() {
+                let x = amphipods
+                    .next()
+                    .ok_or_else(|| crate::Error::parse(input, end_of(line), "an amphipod"))?;
+
+                room[j] = match x {
+                    "A" => 1,
+                    "B" => 2,
+                    "C" => 3,
+                    "D" => 4,
+                    _ => return Err(crate::Error::parse(input, x, "one of A, B, C, D")),
+                };
+            }
+
+            if let Some(x) = amphipods.next() {
+                return Err(crate::Error::parse(input, x, "#"));
+            }
+        }
 
-    println!("Problem 2: {}", find_solution(board2));
+        Ok(Board {
+            hallway: [0; 11],
+            rooms,
+        })
+    }
+
+    fn part1(&board1: &Self::Input) -> crate::Result<Answer> {
+        Ok(find_solution(board1)?.into())
+    }
 
-    Ok(())
+    fn part2(board1: &Self::Input) -> crate::Result<Answer> {
+        Ok(find_solution(unfold(board1))?.into())
+    }
+
+    fn explain(&board1: &Self::Input, part: Part) -> crate::Result<Vec<String>> {
+        match part {
+            Part::One => Ok(render_moves(&find_moves(board1)?)),
+            Part::Two => Ok(render_moves(&find_moves(unfold(&board1))?)),
+        }
+    }
 }
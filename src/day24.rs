@@ -1,11 +1,16 @@
-use ahash::AHashMap;
+use crate::{
+    error::Parsed,
+    solution::{Answer, Solution},
+    util::{
+        parse::{alt, integer, label, literal, map, one_of, pair, parse_all, preceded, terminated},
+        Memo,
+    },
+};
 
-const INPUT: &str = include_str!("../problems/problem24");
-
-type T = i32;
+pub type T = i64;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Instruction {
+pub enum Instruction {
     Inp(Variable),
     Add(Variable, Value),
     Mul(Variable, Value),
@@ -15,7 +20,7 @@ enum Instruction {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Value {
+pub enum Value {
     Literal(T),
     Variable(Variable),
 }
@@ -30,7 +35,7 @@ impl Value {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Variable {
+pub enum Variable {
     W = 0,
     X = 1,
     Y = 2,
@@ -47,80 +52,54 @@ impl Variable {
     }
 }
 
-fn parse_literal(s: &str) -> Option<(T, &str)> {
-    let digit_count = s
-        .chars()
-        .take_while(|c| c.is_digit(10) || *c == '-')
-        .count();
-    let literal = s[..digit_count].parse::<T>().ok()?;
+fn parse_variable(s: &str) -> Parsed<'_, Variable> {
+    const VARIABLES: &[(&str, Variable)] = &[
+        ("w", Variable::W),
+        ("x", Variable::X),
+        ("y", Variable::Y),
+        ("z", Variable::Z),
+    ];
 
-    Some((literal, &s[digit_count..]))
+    one_of(VARIABLES, "one of w, x, y, z")(s)
 }
 
-fn parse_variable(s: &str) -> Option<(Variable, &str)> {
-    if s.len() == 0 {
-        return None;
-    }
-
-    let (c, rem) = s.split_at(1);
-    let var = match c {
-        "w" => Variable::W,
-        "x" => Variable::X,
-        "y" => Variable::Y,
-        "z" => Variable::Z,
-        _ => return None,
-    };
+fn parse_value(s: &str) -> Parsed<'_, Value> {
+    let variable = map(parse_variable, Value::Variable);
+    let literal = map(integer(), Value::Literal);
 
-    Some((var, rem))
-}
-
-fn parse_value(s: &str) -> Option<(Value, &str)> {
-    if let Some((var, rem)) = parse_variable(s) {
-        Some((Value::Variable(var), rem))
-    } else if let Some((l, rem)) = parse_literal(s) {
-        Some((Value::Literal(l), rem))
-    } else {
-        None
-    }
+    label(alt(variable, literal), "a variable or a number")(s)
 }
 
-fn parse_instruction(s: &str) -> Option<(Instruction, &str)> {
-    if s.starts_with("inp ") {
-        let (a, rem) = parse_variable(&s[4..])?;
-
-        Some((Instruction::Inp(a), rem))
-    } else if s.starts_with("add ") {
-        let (a, rem) = parse_variable(&s[4..])?;
-        let (b, rem) = parse_value(&rem[1..])?;
-
-        Some((Instruction::Add(a, b), rem))
-    } else if s.starts_with("mul ") {
-        let (a, rem) = parse_variable(&s[4..])?;
-        let (b, rem) = parse_value(&rem[1..])?;
-
-        Some((Instruction::Mul(a, b), rem))
-    } else if s.starts_with("div ") {
-        let (a, rem) = parse_variable(&s[4..])?;
-        let (b, rem) = parse_value(&rem[1..])?;
-
-        Some((Instruction::Div(a, b), rem))
-    } else if s.starts_with("mod ") {
-        let (a, rem) = parse_variable(&s[4..])?;
-        let (b, rem) = parse_value(&rem[1..])?;
-
-        Some((Instruction::Mod(a, b), rem))
-    } else if s.starts_with("eql ") {
-        let (a, rem) = parse_variable(&s[4..])?;
-        let (b, rem) = parse_value(&rem[1..])?;
+fn parse_instruction(s: &str) -> Parsed<'_, Instruction> {
+    type Constructor = fn(Variable, Value) -> Instruction;
+
+    const OPERATIONS: &[(&str, Constructor)] = &[
+        ("add ", Instruction::Add),
+        ("mul ", Instruction::Mul),
+        ("div ", Instruction::Div),
+        ("mod ", Instruction::Mod),
+        ("eql ", Instruction::Eql),
+    ];
+
+    let inp = map(preceded(literal("inp "), parse_variable), Instruction::Inp);
+    let operation = map(
+        pair(
+            one_of(OPERATIONS, "one of inp, add, mul, div, mod, eql"),
+            pair(terminated(parse_variable, label(literal(" "), "a space")), parse_value),
+        ),
+        |(constructor, (a, b))| constructor(a, b),
+    );
 
-        Some((Instruction::Eql(a, b), rem))
-    } else {
-        None
-    }
+    alt(inp, operation)(s)
 }
 
-/// Run the given until the second input instruction is hit and return the remaining program
-fn run<'a>(instructions: &'a [Instruction], mem: &mut [T; 4], input: T) -> &'a [Instruction] {
+/// Run the given until the second input instruction is hit and return the remaining program.
+/// Returns None if the ALU would crash, i.e. when dividing by zero.
+fn run<'a>(
+    instructions: &'a [Instruction],
+    mem: &mut [T; 4],
+    input: T,
+) -> Option<&'a [Instruction]> {
     let mut input = Some(input);
 
     let mut pc = 0;
@@ -136,54 +115,89 @@ fn run<'a>(instructions: &'a [Instruction], mem: &mut [T; 4], input: T) -> &'a [
                     break;
                 }
             }
+            // Overflows wrap around, also in debug builds
             Instruction::Add(a, b) => {
-                *a.get_mut(mem) += b.get(mem);
+                let b = b.get(mem);
+                let v = a.get_mut(mem);
+
+                *v = v.wrapping_add(b);
             }
             Instruction::Mul(a, b) => {
-                *a.get_mut(mem) *= b.get(mem);
+                let b = b.get(mem);
+                let v = a.get_mut(mem);
+
+                *v = v.wrapping_mul(b);
             }
             Instruction::Div(a, b) => {
-                *a.get_mut(mem) /= b.get(mem);
+                let b = b.get(mem);
+                let v = a.get_mut(mem);
+
+                *v = v.checked_div(b)?;
             }
             Instruction::Mod(a, b) => {
                 let b = b.get(mem);
                 let v = a.get_mut(mem);
 
-                *v = v.rem_euclid(b);
+                *v = v.checked_rem_euclid(b)?;
             }
             Instruction::Eql(a, b) => {
-                *a.get_mut(mem) = (a.get(mem) == b.get(&mem)) as T;
+                *a.get_mut(mem) = (a.get(mem) == b.get(mem)) as T;
             }
         }
 
         pc += 1;
     }
 
-    &instructions[pc..]
+    Some(&instructions[pc..])
 }
 
+type SolutionMemo = Memo<(u8, T), Option<u64>>;
+
+/// For every depth, a bound that z has to stay below for the rest of the program to be able to
+/// bring it back to 0: the product of how much z can shrink in each of the remaining blocks
+fn z_limits(program: &[Instruction]) -> Vec<u64> {
+    let mut shrinks = Vec::new();
+
+    for inst in program {
+        let shrink = match inst {
+            Instruction::Inp(_) => {
+                shrinks.push(1u64);
+                continue;
+            }
+            Instruction::Div(Variable::Z, Value::Literal(d)) => d.unsigned_abs(),
+            // These can take z down to 0 in one go
+            Instruction::Mod(Variable::Z, _)
+            | Instruction::Eql(Variable::Z, _)
+            | Instruction::Mul(Variable::Z, Value::Literal(0)) => u64::MAX,
+            _ => continue,
+        };
+
+        if let Some(last) = shrinks.last_mut() {
+            *last = last.saturating_mul(shrink.max(1));
+        }
+    }
+
+    let mut limits = vec![1u64; shrinks.len() + 1];
+    for (i, shrink) in shrinks.iter().enumerate().rev() {
+        limits[i] = limits[i + 1].saturating_mul(*shrink);
+    }
+
+    limits
+}
+
+/// The smallest or biggest input (with its digits reversed) that makes the program, which is
+/// at its depth-th input instruction, finish with z = 0
 fn find_solution(
     program: &[Instruction],
-    depth: u8,
-    z: T,
-    cache: &mut AHashMap<(u8, T), Option<u64>>,
     biggest: bool,
+    limits: &[u64],
+    memo: &mut SolutionMemo,
+    (depth, z): (u8, T),
 ) -> Option<u64> {
-    if program.len() == 0 {
+    if program.is_empty() {
         return if z == 0 { Some(0) } else { None };
     }
 
-    // (*)
-    if z as u64 > 27u64.pow(14 - depth as u32) {
-        return None;
-    }
-
-    if let Some(&ret) = cache.get(&(depth, z)) {
-        return ret;
-    }
-
-    let mut ret = None;
-
     let input = if biggest {
         [9, 8, 7, 6, 5, 4, 3, 2, 1]
     } else {
@@ -194,19 +208,39 @@ fn find_solution(
         let mut mem = [0, 0, 0, 0];
         mem[Variable::Z as usize] = z;
 
-        let remaining_program = run(&program, &mut mem, w);
+        let remaining_program = match run(program, &mut mem, w) {
+            Some(remaining_program) => remaining_program,
+            None => continue,
+        };
 
         let nz = mem[Variable::Z as usize];
 
-        if let Some(val) = find_solution(remaining_program, depth + 1, nz, cache, biggest) {
-            ret = Some(10 * val + (w as u64));
-            break;
+        // (*), checked before the cache lookup to keep hopeless states out of the cache. A
+        // negative z converts to a huge number and is given up as well.
+        let limit = limits.get(depth as usize + 1).copied().unwrap_or(1);
+        if nz as u64 >= limit {
+            continue;
+        }
+
+        // The remaining program only depends on the depth, so it is fine to leave it out of
+        // the key
+        let val = memo.call((depth + 1, nz), &|memo, key| {
+            find_solution(remaining_program, biggest, limits, memo, key)
+        });
+
+        if let Some(val) = val {
+            return Some(10 * val + (w as u64));
         }
     }
 
-    cache.insert((depth, z), ret);
+    None
+}
+
+/// The biggest or smallest model number accepted by the program
+fn model_number(program: &[Instruction], biggest: bool) -> Option<u64> {
+    let limits = z_limits(program);
 
-    ret
+    find_solution(program, biggest, &limits, &mut Memo::new(), (0, 0)).map(reverse_10)
 }
 
 fn reverse_10(mut u: u64) -> u64 {
@@ -221,12 +255,17 @@ fn reverse_10(mut u: u64) -> u64 {
     ret
 }
 
-pub fn solve() -> crate::Result<()> {
-    let instructions = INPUT
-        .lines()
-        .map(|line| parse_instruction(&line).map(|(i, _)| i))
-        .collect::<Option<Vec<Instruction>>>()
-        .ok_or(crate::Error::InvalidInput)?;
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        input
+            .lines()
+            .map(|line| parse_all(input, line, parse_instruction))
+            .collect()
+    }
 
     // This solution tries to be as general as possible, never the less, we assume a few things
     // about the input:
@@ -235,22 +274,20 @@ pub fn solve() -> crate::Result<()> {
     // Finally, there is an optimization (*), which we could leave out and the program
     // would still work - just much slower - that assumes
     // - in each block (by which we mean a section starting with inp and otherwise
-    // having no other inp's) the variable z can only shrink by a factor of 27  i.e.
-    // if z0 is before the block is run, and z1 is afterwards, we assume that 27 * z0 >= z1
-
-    println!(
-        "Problem 1: {:?}",
-        find_solution(&instructions, 0, 0, &mut Default::default(), true)
-            .map(reverse_10)
-            .unwrap()
-    );
+    // having no other inp's) the variable z can only shrink through its `div z d`
+    // instructions i.e. if z0 is before the block is run, and z1 is afterwards, we assume
+    // that z1 >= z0 / d, and z1 >= z0 in blocks without one. Blocks that take z modulo
+    // something, compare it or multiply it with 0 are not bounded.
 
-    println!(
-        "Problem 2: {:?}",
-        find_solution(&instructions, 0, 0, &mut Default::default(), false)
-            .map(reverse_10)
-            .unwrap()
-    );
+    fn part1(instructions: &Self::Input) -> crate::Result<Answer> {
+        let prob1 = model_number(instructions, true).ok_or(crate::Error::NoSolution)?;
+
+        Ok(prob1.into())
+    }
+
+    fn part2(instructions: &Self::Input) -> crate::Result<Answer> {
+        let prob2 = model_number(instructions, false).ok_or(crate::Error::NoSolution)?;
 
-    Ok(())
+        Ok(prob2.into())
+    }
 }
@@ -1,165 +1,129 @@
-use std::{
-    fmt::Display,
-    ops::{Index, IndexMut},
-};
+use std::fmt::Display;
 
-const INPUT: &str = include_str!("../problems/problem25");
+use crate::{
+    solution::{Answer, Solution},
+    util::Grid,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum Direction {
+pub enum Direction {
     East,
     South,
 }
 
-#[derive(Clone)]
-struct Grid {
-    spaces: Vec<Option<Direction>>,
-    width: usize,
-    height: usize,
+/// The positions of the sea cucumbers, and which way they are facing
+pub type SeaFloor = Grid<Option<Direction>>;
+
+/// Draw the sea floor the way the input does
+pub fn display(floor: &SeaFloor) -> impl Display + '_ {
+    floor.display_with(|space| match space {
+        Some(Direction::East) => '>',
+        Some(Direction::South) => 'v',
+        None => '.',
+    })
 }
 
-impl Index<(usize, usize)> for Grid {
-    type Output = Option<Direction>;
+fn step_east(floor: &mut SeaFloor) -> usize {
+    let mut changed = 0;
+    let (width, height) = (floor.width(), floor.height());
 
-    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
-        &self.spaces[x + y * self.width]
-    }
-}
+    let first_col: Vec<_> = floor.column(0).copied().collect();
 
-impl IndexMut<(usize, usize)> for Grid {
-    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
-        &mut self.spaces[x + y * self.width]
-    }
-}
+    for y in 0..height {
+        let mut skip_next = false;
+        for x in 0..width {
+            if skip_next {
+                skip_next = false;
+                continue;
+            }
 
-impl Display for Grid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let letter = match self[(x, y)] {
-                    Some(Direction::East) => '>',
-                    Some(Direction::South) => 'v',
-                    None => '.',
-                };
-
-                write!(f, "{}", letter)?;
+            if floor[(x, y)] != Some(Direction::East) {
+                continue;
             }
-            writeln!(f)?;
-        }
 
-        Ok(())
+            if x + 1 < width && floor[(x + 1, y)].is_none() {
+                floor[(x + 1, y)] = floor[(x, y)].take();
+                skip_next = true;
+                changed += 1;
+            } else if x + 1 == width && first_col[y].is_none() {
+                floor[(0, y)] = floor[(x, y)].take();
+                changed += 1;
+            }
+        }
     }
+
+    changed
 }
 
-impl Grid {
-    fn step_east(&mut self) -> usize {
-        let mut changed = 0;
-
-        let first_col: Vec<_> = (0..self.height)
-            .map(|y| self[(0, y)])
-            .collect();
-
-        for y in 0..self.height {
-            let mut skip_next = false;
-            for x in 0..self.width {
-                if skip_next {
-                    skip_next = false;
-                    continue;
-                }
-
-                if self[(x, y)] != Some(Direction::East) {
-                    continue;
-                }
-                
-                if x + 1 < self.width && self[(x + 1, y)].is_none()  {
-                    self[(x + 1, y)] = self[(x, y)].take();
-                    skip_next = true;
-                    changed += 1;
-                } else if x + 1 == self.width && first_col[y].is_none() {
-                    self[(0, y)] = self[(x, y)].take();
-                    changed += 1;
-                }
+fn step_south(floor: &mut SeaFloor) -> usize {
+    // Similar to step_east
+    let mut changed = 0;
+    let (width, height) = (floor.width(), floor.height());
+
+    let first_row = floor.row(0).to_vec();
+
+    for x in 0..width {
+        let mut skip_next = false;
+        for y in 0..height {
+            if skip_next {
+                skip_next = false;
+                continue;
             }
-        }
 
-        changed
-    }
+            if floor[(x, y)] != Some(Direction::South) {
+                continue;
+            }
 
-    fn step_south(&mut self) -> usize {
-        // Similar to step_east
-        let mut changed = 0;
-
-        let first_row: Vec<_> = (0..self.width)
-            .map(|x| self[(x, 0)])
-            .collect();
-
-        for x in 0..self.width {
-            let mut skip_next = false;
-            for y in 0..self.height {
-                if skip_next {
-                    skip_next = false;
-                    continue;
-                }
-
-                if self[(x, y)] != Some(Direction::South) {
-                    continue;
-                }
-                
-                if y + 1 < self.height && self[(x, y + 1)].is_none()  {
-                    self[(x, y + 1)] = self[(x, y)].take();
-                    skip_next = true;
-                    changed += 1;
-                } else if y + 1 == self.height && first_row[x].is_none() {
-                    self[(x, 0)] = self[(x, y)].take();
-                    changed += 1;
-                }
+            if y + 1 < height && floor[(x, y + 1)].is_none() {
+                floor[(x, y + 1)] = floor[(x, y)].take();
+                skip_next = true;
+                changed += 1;
+            } else if y + 1 == height && first_row[x].is_none() {
+                floor[(x, 0)] = floor[(x, y)].take();
+                changed += 1;
             }
         }
-
-        changed
     }
 
-    fn step(&mut self) -> usize {
-        self.step_east() + self.step_south()
-    }
+    changed
 }
 
-pub fn solve() -> crate::Result<()> {
-    let lines = INPUT.lines();
-
-    let mut spaces = Vec::new();
-    let mut width = 0;
+/// Move both herds once and return how many sea cucumbers moved
+fn step(floor: &mut SeaFloor) -> usize {
+    step_east(floor) + step_south(floor)
+}
 
-    for line in lines {
-        if width == 0 {
-            width = line.len();
-        }
+pub struct Day25;
 
-        for c in line.chars() {
-            let dir = match c {
-                '.' => None,
-                '>' => Some(Direction::East),
-                'v' => Some(Direction::South),
-                _ => return Err(crate::Error::InvalidInput),
-            };
+impl Solution for Day25 {
+    type Input = SeaFloor;
 
-            spaces.push(dir);
-        }
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        Grid::parse(input, "one of . > v", |c| match c {
+            '.' => Some(None),
+            '>' => Some(Some(Direction::East)),
+            'v' => Some(Some(Direction::South)),
+            _ => None,
+        })
     }
 
-    let mut grid = Grid {
-        height: spaces.len() / width,
-        width,
-        spaces,
-    };
-
-    for i in 1.. {
-        let changed = grid.step();
-        if changed == 0 {
-            println!("Problem 1: {}", i);
-            break;
+    fn part1(floor: &Self::Input) -> crate::Result<Answer> {
+        // Some herds keep moving forever, so give up at some point
+        const MAX_STEPS: u64 = 100_000;
+
+        let mut floor = floor.clone();
+
+        for steps in 1..=MAX_STEPS {
+            if step(&mut floor) == 0 {
+                return Ok(steps.into());
+            }
         }
+
+        Err(crate::Error::NoSolution)
     }
 
-    Ok(())
+    fn part2(_: &Self::Input) -> crate::Result<Answer> {
+        // There is no second puzzle on the last day
+        Ok(Answer::None)
+    }
 }
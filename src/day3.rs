@@ -1,23 +1,49 @@
-const INPUT: &str = include_str!("../problems/problem3");
+use crate::solution::{Answer, Solution};
 
 pub fn parse_bitstring(s: &str) -> crate::Result<Vec<u8>> {
-    let res = s
-        .chars()
-        .map(|x| x.to_digit(2).map(|val| val as u8))
-        .collect::<Option<Vec<u8>>>();
-
-    res.ok_or(crate::Error::InvalidInput)
+    s.char_indices()
+        .map(|(i, x)| match x.to_digit(2) {
+            Some(val) => Ok(val as u8),
+            None => Err(crate::Error::parse(s, &s[i..i + x.len_utf8()], "0 or 1")),
+        })
+        .collect()
 }
 
-pub fn solve() -> crate::Result<()> {
-    let xs = INPUT.lines()
-        .map(|line| parse_bitstring(&line))
-        .collect::<crate::Result<Vec<_>>>()?;
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Vec<u8>>;
 
-    println!("Problem 1: {}", solve1(&xs)?);
-    println!("Problem 2: {}", solve2(&xs)?);
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let first = input.lines().next().unwrap_or_default();
+        let width = first.len();
 
-    Ok(())
+        // The numbers, and the mask of their bits, have to fit into a u64
+        if width > 63 {
+            return Err(crate::Error::parse(input, first, "at most 63 bits"));
+        }
+
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                if line.len() != width {
+                    let expected = format!("{} bits", width);
+                    return Err(crate::Error::parse(input, line, expected));
+                }
+
+                parse_bitstring(line).map_err(|e| e.on_line(i + 1))
+            })
+            .collect()
+    }
+
+    fn part1(xs: &Self::Input) -> crate::Result<Answer> {
+        Ok(solve1(xs)?.into())
+    }
+
+    fn part2(xs: &Self::Input) -> crate::Result<Answer> {
+        Ok(solve2(xs)?.into())
+    }
 }
 
 fn most_common_bit<I: IntoIterator<Item=u8>>(bits: I) -> u8 {
@@ -32,9 +58,9 @@ fn most_common_bit<I: IntoIterator<Item=u8>>(bits: I) -> u8 {
     }
 
     if res >= 0 {
-        return 1;
+        1
     } else {
-        return 0;
+        0
     }
 }
 
@@ -49,8 +75,8 @@ fn bits_to_number<I: IntoIterator<Item=u8>>(bits: I) -> u64 {
     res
 }
 
-fn solve1(xs: &Vec<Vec<u8>>) -> crate::Result<u64> {
-    let n = xs.get(0).ok_or(crate::Error::NoInput)?.len();
+fn solve1(xs: &[Vec<u8>]) -> crate::Result<u64> {
+    let n = xs.first().ok_or(crate::Error::NoInput)?.len();
     
     let most_common_bits = (0..n)
         .map(|i| most_common_bit(xs.iter().map(|x| x[i])));
@@ -58,11 +84,11 @@ fn solve1(xs: &Vec<Vec<u8>>) -> crate::Result<u64> {
     let gamma = bits_to_number(most_common_bits);
     let epsilon = (1 << n) - 1 - gamma;
 
-    return Ok(gamma * epsilon);
+    gamma.checked_mul(epsilon).ok_or(crate::Error::InvalidInput)
 }
 
-fn select(xs: &Vec<Vec<u8>>, select_most_common: bool) -> crate::Result<u64> {
-    let n = xs.get(0).ok_or(crate::Error::NoInput)?.len();
+fn select(xs: &[Vec<u8>], select_most_common: bool) -> crate::Result<u64> {
+    let n = xs.first().ok_or(crate::Error::NoInput)?.len();
 
     let mut selected = vec![true; xs.len()];
     let mut selected_count = xs.len();
@@ -92,7 +118,7 @@ fn select(xs: &Vec<Vec<u8>>, select_most_common: bool) -> crate::Result<u64> {
                     .filter(|&(i, _)| selected[i])
                     .map(|(_, x)| x)
                     .next()
-                    .ok_or(crate::Error::InvalidInput)?;
+                    .ok_or(crate::Error::NoSolution)?;
 
                 let ret = bits_to_number(selected_number.iter().copied());
 
@@ -101,12 +127,14 @@ fn select(xs: &Vec<Vec<u8>>, select_most_common: bool) -> crate::Result<u64> {
         }
     }
 
-    Err(crate::Error::InvalidInput)
+    Err(crate::Error::NoSolution)
 }
 
-fn solve2(xs: &Vec<Vec<u8>>) -> crate::Result<u64> {
+fn solve2(xs: &[Vec<u8>]) -> crate::Result<u64> {
     let oxygen_generator_rating = select(xs, true)?;
     let c02_scrubber_rating = select(xs, false)?;
 
-    Ok(oxygen_generator_rating * c02_scrubber_rating)
+    oxygen_generator_rating
+        .checked_mul(c02_scrubber_rating)
+        .ok_or(crate::Error::InvalidInput)
 }
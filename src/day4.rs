@@ -1,14 +1,18 @@
 use std::collections::HashMap;
 
-const INPUT: &str = include_str!("../problems/problem4");
+use crate::{
+    error::{end_of, parse_number},
+    solution::{Answer, Solution},
+};
+
 const INF: usize = usize::MAX;
 
 fn determine_winning_move_count(
-    board: &Vec<u32>,
-    moves: &Vec<u32>,
+    board: &[u32],
+    moves: &[u32],
     moves_map: &HashMap<u32, usize>,
     width: usize,
-) -> Option<(usize, u32)> {
+) -> Option<(usize, crate::Result<u64>)> {
     let marked_after = board
         .iter()
         .map(|x| moves_map.get(x).copied().unwrap_or(INF))
@@ -38,77 +42,125 @@ fn determine_winning_move_count(
 
     if can_win_after < INF {
         // Determine the score
-        let unmarked_sum: u32 = marked_after
+        let unmarked_sum: u64 = marked_after
             .iter()
             .zip(board)
             .filter(|(&v, _)| v > can_win_after)
-            .map(|(_, &x)| x)
+            .map(|(_, &x)| u64::from(x))
             .sum();
 
-        Some((can_win_after, unmarked_sum * moves[can_win_after]))
+        let score = unmarked_sum
+            .checked_mul(moves[can_win_after].into())
+            .ok_or(crate::Error::InvalidInput);
+
+        Some((can_win_after, score))
     } else {
         None
     }
 }
 
-pub fn solve() -> crate::Result<()> {
-    const BOARD_ENTRIES: usize = 5 * 5;
+pub struct Bingo {
+    moves: Vec<u32>,
+    moves_map: HashMap<u32, usize>,
+    boards: Vec<Vec<u32>>,
+}
 
-    let mut lines = INPUT.lines().chain(std::iter::once("".into()));
+impl Bingo {
+    /// The move count after which each board that can win at all wins, together with its
+    /// score, or an error if the score does not fit into a u64
+    fn winning_boards(&self) -> impl Iterator<Item = (usize, crate::Result<u64>)> + '_ {
+        self.boards.iter().filter_map(move |board| {
+            determine_winning_move_count(board, &self.moves, &self.moves_map, 5)
+        })
+    }
+}
 
-    let moves = lines
-        .next()
-        .ok_or(crate::Error::NoInput)?
-        .split(",")
-        .map(|x| x.parse::<u32>())
-        .collect::<Result<Vec<_>, _>>()?;
+pub struct Day4;
 
-    let moves_map = moves
-        .iter()
-        .enumerate()
-        .map(|(i, &x)| (x, i))
-        .collect::<HashMap<u32, usize>>();
-
-    // Skip following whitespace
-    let _ = lines.next();
-
-    let mut boards: Vec<Vec<u32>> = Vec::new();
-    let mut current_board: Vec<u32> = Vec::with_capacity(BOARD_ENTRIES);
-
-    for line in lines {
-        if line.trim().is_empty() {
-            let old = std::mem::replace(&mut current_board, Vec::with_capacity(BOARD_ENTRIES));
-            boards.push(old);
-        } else {
-            for x in line.split_whitespace() {
-                let x = x.parse::<u32>()?;
-                current_board.push(x);
-            }
-        }
-    }
+impl Solution for Day4 {
+    type Input = Bingo;
 
-    let mut final_score_1 = 0;
-    let mut winning_move_count_1 = INF;
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        const BOARD_ENTRIES: usize = 5 * 5;
 
-    let mut final_score_2 = 0;
-    let mut winning_move_count_2 = 0;
+        let mut lines = input.lines().chain(std::iter::once(""));
 
-    for board in boards {
-        if let Some((count, score)) = determine_winning_move_count(&board, &moves, &moves_map, 5) {
-            if count < winning_move_count_1 {
-                final_score_1 = score;
-                winning_move_count_1 = count;
-            }
+        let moves = lines
+            .next()
+            .ok_or(crate::Error::NoInput)?
+            .split(',')
+            .map(|x| parse_number::<u32>(input, x))
+            .collect::<crate::Result<Vec<_>>>()?;
 
-            if count > winning_move_count_2 || winning_move_count_2 == 0 {
-                final_score_2 = score;
-                winning_move_count_2 = count;
+        let moves_map = moves
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| (x, i))
+            .collect::<HashMap<u32, usize>>();
+
+        // Skip following whitespace
+        let _ = lines.next();
+
+        let mut boards: Vec<Vec<u32>> = Vec::new();
+        let mut current_board: Vec<u32> = Vec::with_capacity(BOARD_ENTRIES);
+        let mut last_line = "";
+
+        for line in lines {
+            if line.trim().is_empty() {
+                if current_board.is_empty() {
+                    continue;
+                }
+
+                if current_board.len() != BOARD_ENTRIES {
+                    return Err(crate::Error::parse(input, end_of(last_line), "5 rows of 5 numbers"));
+                }
+
+                let old = std::mem::replace(&mut current_board, Vec::with_capacity(BOARD_ENTRIES));
+                boards.push(old);
+            } else {
+                if current_board.len() == BOARD_ENTRIES {
+                    return Err(crate::Error::parse(input, line, "an empty line"));
+                }
+
+                last_line = line;
+
+                let row = line
+                    .split_whitespace()
+                    .map(|x| parse_number::<u32>(input, x))
+                    .collect::<crate::Result<Vec<_>>>()?;
+
+                if row.len() != 5 {
+                    return Err(crate::Error::parse(input, line, "5 numbers"));
+                }
+
+                current_board.extend(row);
             }
         }
+
+        Ok(Bingo {
+            moves,
+            moves_map,
+            boards,
+        })
     }
 
-    println!("Problem 1: {}", final_score_1);
-    println!("Problem 2: {}", final_score_2);
+    fn part1(bingo: &Self::Input) -> crate::Result<Answer> {
+        // The first board to win
+        let (_, final_score) = bingo
+            .winning_boards()
+            .min_by_key(|&(count, _)| count)
+            .ok_or(crate::Error::NoSolution)?;
 
-    Ok(())
+        Ok(final_score?.into())
+    }
+
+    fn part2(bingo: &Self::Input) -> crate::Result<Answer> {
+        // The last board to win, the first of them on a tie
+        let (_, final_score) = bingo
+            .winning_boards()
+            .min_by_key(|&(count, _)| std::cmp::Reverse(count))
+            .ok_or(crate::Error::NoSolution)?;
+
+        Ok(final_score?.into())
+    }
 }
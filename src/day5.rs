@@ -1,53 +1,89 @@
 use std::collections::HashMap;
 
-const INPUT: &str = include_str!("../problems/problem5");
+use crate::{
+    error::{end_of, parse_number},
+    solution::{Answer, Solution},
+    util::Vec2,
+};
 
-pub fn solve() -> crate::Result<()> {
-    let lines = INPUT.lines();
+pub type Line = (Vec2<i32>, Vec2<i32>);
 
-    let mut hit_count_1: HashMap<(i32, i32), usize> = HashMap::new();
-    let mut hit_count_2: HashMap<(i32, i32), usize> = HashMap::new();
+/// Count the points where at least two lines overlap. Diagonal lines are only
+/// considered if `diagonals` is set.
+fn count_overlaps(lines: &[Line], diagonals: bool) -> usize {
+    let mut hit_count: HashMap<Vec2<i32>, usize> = HashMap::new();
 
-    for line in lines {
-        let mut nrs = line.split(" -> ")
-            .flat_map(|p| p.split(","))
-            .map(|x| x.parse::<i32>());
+    for &(start, end) in lines {
+        let step = (end - start).signum();
 
-        let x1 = nrs.next().ok_or(crate::Error::NoInput)??;
-        let y1 = nrs.next().ok_or(crate::Error::NoInput)??;
-        let x2 = nrs.next().ok_or(crate::Error::NoInput)??;
-        let y2 = nrs.next().ok_or(crate::Error::NoInput)??;
+        let straight = step.x == 0 || step.y == 0;
 
-        let dx = (x2 - x1).signum();
-        let dy = (y2 - y1).signum();
-
-        let straight = dx == 0 || dy == 0;
+        if !straight && !diagonals {
+            continue;
+        }
 
-        let (mut x, mut y) = (x1, y1);
+        let mut p = start;
 
         loop {
-            if straight {
-                let e = hit_count_1.entry((x, y)).or_insert(0);
-                *e += 1;
-            }
-
-            let e = hit_count_2.entry((x, y)).or_insert(0);
+            let e = hit_count.entry(p).or_insert(0);
             *e += 1;
 
-            if x == x2 && y == y2 {
+            if p == end {
                 break;
             }
 
-            x += dx;
-            y += dy;
+            p += step;
         }
     }
 
-    let prob1 = hit_count_1.values().filter(|&&v| v >= 2).count();
-    let prob2 = hit_count_2.values().filter(|&&v| v >= 2).count();
+    hit_count.values().filter(|&&v| v >= 2).count()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<Line>;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let mut lines = Vec::new();
+
+        for line in input.lines() {
+            let mut nrs = line.split(" -> ")
+                .flat_map(|p| p.split(','))
+                .map(|x| parse_number::<i32>(input, x));
+            let mut next = || {
+                nrs.next()
+                    .unwrap_or_else(|| Err(crate::Error::parse(input, end_of(line), "x1,y1 -> x2,y2")))
+            };
+
+            let x1 = next()?;
+            let y1 = next()?;
+            let x2 = next()?;
+            let y2 = next()?;
 
-    println!("Problem 1: {}", prob1);
-    println!("Problem 2: {}", prob2);
+            let (start, end) = (Vec2::new(x1, y1), Vec2::new(x2, y2));
+            let d = end - start;
 
-    Ok(())
-}
\ No newline at end of file
+            // count_overlaps walks the line point by point, which only ends for these
+            if d.x != 0 && d.y != 0 && d.x.abs() != d.y.abs() {
+                return Err(crate::Error::parse(
+                    input,
+                    line,
+                    "a horizontal, vertical or 45 degree line",
+                ));
+            }
+
+            lines.push((start, end));
+        }
+
+        Ok(lines)
+    }
+
+    fn part1(lines: &Self::Input) -> crate::Result<Answer> {
+        Ok(count_overlaps(lines, false).into())
+    }
+
+    fn part2(lines: &Self::Input) -> crate::Result<Answer> {
+        Ok(count_overlaps(lines, true).into())
+    }
+}
@@ -1,94 +1,128 @@
 use std::collections::HashMap;
 
-const INPUT: &str = include_str!("../problems/problem6");
+use crate::{
+    error::parse_number,
+    solution::{Answer, Solution},
+    solvers::Variant,
+    util::{Memo, Store},
+};
+
+pub const VARIANTS: &[Variant] = &[
+    Variant {
+        name: "dp-table",
+        solution: &DpTable,
+    },
+    Variant {
+        name: "dp-hashmap",
+        solution: &DpHashmap,
+    },
+    Variant {
+        name: "memoized-recursion",
+        solution: &MemoizedRecursion,
+    },
+];
+
+/// Solves both parts with [`solve_memoized_recursion_table`]
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<u8>;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let fish = input.lines().next()
+            .ok_or(crate::Error::NoInput)?
+            .split(',')
+            .map(|x| match parse_number::<u8>(input, x)? {
+                timer @ 0..=8 => Ok(timer),
+                _ => Err(crate::Error::parse(input, x, "a timer from 0 to 8")),
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        Ok(fish)
+    }
 
-pub fn solve() -> crate::Result<()> {
-    let fish = INPUT.lines().next()
-        .ok_or(crate::Error::NoInput)?
-        .split(",")
-        .map(|x| x.parse::<u8>())
-        .collect::<Result<Vec<_>, _>>()?;
+    // `aoc2021 bench --variants 6` compares this to the other ways of counting the fish.
+    // Conclusion: recursion and hash maps are slower than filling an array bottom-up, but
+    // still fast enough (especially in the context of competitive programming)
 
-    let (prob1, prob2) = solve_memoized_recursion_table(&fish)?;
+    fn part1(fish: &Self::Input) -> crate::Result<Answer> {
+        Ok(solve_memoized_recursion_table(fish, 80).into())
+    }
 
-    // solve_dp_table takes around 0.6 ms (662900 ns)
-    // solve_momoized_recursion_table takes around 0.6 ms  (675900 ns)
-    // solve_dp_hashmap takes around 1.0ms (1009100 ns)
-    // solve_momoized_recursion takes around 1.1 ms  (1142700 ns)
+    fn part2(fish: &Self::Input) -> crate::Result<Answer> {
+        Ok(solve_memoized_recursion_table(fish, 256).into())
+    }
+}
 
-    // Conclusion: recursion is acceptable, using a hashmap instead of an array
-    // is around two times slower (which is also acceptable, especially in the context of
-    // competitive programming)
+/// A variant of [`Day6`] that counts the fish with another function, the input is parsed the
+/// same way
+macro_rules! variant {
+    ($name:ident, $count:ident) => {
+        #[doc = concat!("Solves both parts with [`", stringify!($count), "`]")]
+        pub struct $name;
 
-    println!("Problem 1: {}", prob1);
-    println!("Problem 2: {}", prob2);
+        impl Solution for $name {
+            type Input = Vec<u8>;
 
-    Ok(())
+            fn parse(input: &str) -> crate::Result<Self::Input> {
+                Day6::parse(input)
+            }
+
+            fn part1(fish: &Self::Input) -> crate::Result<Answer> {
+                Ok($count(fish, 80).into())
+            }
+
+            fn part2(fish: &Self::Input) -> crate::Result<Answer> {
+                Ok($count(fish, 256).into())
+            }
+        }
+    };
 }
 
-fn solve_dp_table(fish: &Vec<u8>) -> crate::Result<(u64, u64)> {
-    const DAYS: usize = 256;
-    let mut dp = vec![0u64; (DAYS + 1) * 9];
+variant!(DpTable, solve_dp_table);
+variant!(DpHashmap, solve_dp_hashmap);
+variant!(MemoizedRecursion, solve_memoized_recursion);
+
+/// Count the fish after the given number of days, filling a table bottom-up
+pub fn solve_dp_table(fish: &[u8], days: u16) -> u64 {
+    let days = days as usize;
+    let mut dp = vec![0u64; (days + 1) * 9];
 
     // dp[x + n * 9] will the number of fish the fish x turns into after n days
-    for x in 0..=8 {
-        dp[x] = 1;
+    for v in dp.iter_mut().take(9) {
+        *v = 1;
     }
 
     // Now, fill each row of the dp table
-    for i in 1..=DAYS {
-        dp[0 + i * 9] = dp[6 + (i - 1) * 9] + dp[8 + (i - 1) * 9];
+    for i in 1..=days {
+        dp[i * 9] = dp[6 + (i - 1) * 9] + dp[8 + (i - 1) * 9];
 
         for x in 1..=8 {
             dp[x + i * 9] = dp[(x - 1) + (i - 1) * 9];
         }
     }
 
-    let prob1: u64 = fish.iter().map(|&x| dp[x as usize + 80 * 9]).sum();
-    let prob2: u64 = fish.iter().map(|&x| dp[x as usize + 256 * 9]).sum();
-
-    Ok((prob1, prob2))
+    fish.iter().map(|&x| dp[x as usize + days * 9]).sum()
 }
 
-fn solve_memoized_recursion_table(fish: &Vec<u8>) -> crate::Result<(u64, u64)> {
-    const DAYS: u16 = 256;
-
-    let mut cache = vec![0u64; (DAYS as usize + 1) * 9];
-
-    fn f(x: u8, n: u16, cache: &mut Vec<u64>) -> u64 {
-        let idx = (x as usize) + (n as usize) * 9;
-        if cache[idx] != 0 {
-            return cache[idx];
-        }
+/// Count the fish after the given number of days, memoizing the recursion in a table
+pub fn solve_memoized_recursion_table(fish: &[u8], days: u16) -> u64 {
+    let mut memo = Memo::table((days as usize + 1) * 9, |&(x, n)| {
+        (x as usize) + (n as usize) * 9
+    });
 
-        let ret = if n == 0 {
-            1
-        } else if x == 0 {
-            f(6, n - 1, cache) + f(8, n - 1, cache)
-        } else {
-            f(x - 1, n - 1, cache)
-        };
-
-        cache[idx] = ret;
-        ret
-    }
-
-    let prob1: u64 = fish.iter().map(|&x| f(x, 80, &mut cache)).sum();
-    let prob2: u64 = fish.iter().map(|&x| f(x, 256, &mut cache)).sum();
-
-    Ok((prob1, prob2))
+    fish.iter().map(|&x| memo.call((x, days), &f)).sum()
 }
 
-fn solve_dp_hashmap(fish: &Vec<u8>) -> crate::Result<(u64, u64)> {
-    const DAYS: u16 = 256;
-
+/// Like [`solve_dp_table`], but the table is a hash map
+pub fn solve_dp_hashmap(fish: &[u8], days: u16) -> u64 {
     let mut dp: HashMap<(u8, u16), u64> = Default::default();
 
     for x in 0..=8 {
         dp.insert((x, 0), 1);
     }
 
-    for i in 1..=DAYS {
+    for i in 1..=days {
         dp.insert((0, i), dp[&(6, (i - 1))] + dp[&(8, (i - 1))]);
 
         for x in 1..=8 {
@@ -96,35 +130,23 @@ fn solve_dp_hashmap(fish: &Vec<u8>) -> crate::Result<(u64, u64)> {
         }
     }
 
-    let prob1: u64 = fish.iter().map(|&x| dp[&(x, 80)]).sum();
-    let prob2: u64 = fish.iter().map(|&x| dp[&(x, 256)]).sum();
-
-    Ok((prob1, prob2))
+    fish.iter().map(|&x| dp[&(x, days)]).sum()
 }
 
-fn solve_memoized_recursion(fish: &Vec<u8>) -> crate::Result<(u64, u64)> {
-    const DAYS: u16 = 256;
+/// Like [`solve_memoized_recursion_table`], but the cache is a hash map
+pub fn solve_memoized_recursion(fish: &[u8], days: u16) -> u64 {
+    let mut memo = Memo::new();
 
-    let mut cache: HashMap<(u8, u16), u64> = Default::default();
-    fn f(x: u8, n: u16, cache: &mut HashMap<(u8, u16), u64>) -> u64 {
-        if let Some(ret) = cache.get(&(x, n)) {
-            return *ret;
-        }
-
-        let ret = if n == 0 {
-            1
-        } else if x == 0 {
-            f(6, n - 1, cache) + f(8, n - 1, cache)
-        } else {
-            f(x - 1, n - 1, cache)
-        };
+    fish.iter().map(|&x| memo.call((x, days), &f)).sum()
+}
 
-        cache.insert((x, n), ret);
-        ret
+// f((x, n)) is the number of fish that a fish with timer x turns into after n days
+fn f<S: Store<(u8, u16), u64>>(memo: &mut Memo<(u8, u16), u64, S>, (x, n): (u8, u16)) -> u64 {
+    if n == 0 {
+        1
+    } else if x == 0 {
+        memo.call((6, n - 1), &f) + memo.call((8, n - 1), &f)
+    } else {
+        memo.call((x - 1, n - 1), &f)
     }
-
-    let prob1: u64 = fish.iter().map(|&x| f(x, 80, &mut cache)).sum();
-    let prob2: u64 = fish.iter().map(|&x| f(x, 256, &mut cache)).sum();
-
-    Ok((prob1, prob2))
-}
\ No newline at end of file
+}
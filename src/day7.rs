@@ -1,54 +1,78 @@
-const INPUT: &str = include_str!("../problems/problem7");
+use crate::{
+    error::parse_number,
+    solution::{Answer, Solution},
+};
 
-pub fn solve() -> crate::Result<()> {
-    let line = INPUT
-        .lines()
-        .next()
-        .ok_or(crate::Error::NoInput)?;
+pub struct Day7;
 
-    let crabs = line
-        .split(',')
-        .map(|x| x.parse::<i32>())
-        .collect::<Result<Vec<_>, _>>()?;
+impl Solution for Day7 {
+    type Input = Vec<i32>;
 
-    // Initialze a few values
-    let max = crabs.iter().copied().max().ok_or(crate::Error::NoInput)?;
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let line = input
+            .lines()
+            .next()
+            .ok_or(crate::Error::NoInput)?;
 
-    let prob1 = minimize(&crabs, max, |x: i32, i: i32| (x - i).abs());
+        let crabs = line
+            .split(',')
+            .map(|x| parse_number::<i32>(input, x))
+            .collect::<crate::Result<Vec<_>>>()?;
 
-    let prob2 = minimize(&crabs, max, |x: i32, i: i32| {
-        let n = (x - i).abs();
+        Ok(crabs)
+    }
+
+    fn part1(crabs: &Self::Input) -> crate::Result<Answer> {
+        let max = crabs.iter().copied().max().ok_or(crate::Error::NoInput)?;
+
+        let prob1 = minimize(crabs, max, |x: i64, i: i64| Some((x - i).abs()))?;
+
+        Ok(prob1.into())
+    }
 
-        // Note: this should really be 0.5 * n * (n + 1) but we have factored this out to
-        // to save on multiplication cost
-        n * (n + 1)
-    }) / 2;
+    fn part2(crabs: &Self::Input) -> crate::Result<Answer> {
+        let max = crabs.iter().copied().max().ok_or(crate::Error::NoInput)?;
 
-    println!("Problem 1: {}", prob1);
-    println!("Problem 2: {}", prob2);
+        let prob2 = minimize(crabs, max, |x: i64, i: i64| {
+            let n = (x - i).abs();
 
-    Ok(())
+            // Note: this should really be 0.5 * n * (n + 1) but we have factored this out to
+            // to save on multiplication cost
+            n.checked_mul(n + 1)
+        })? / 2;
+
+        Ok(prob2.into())
+    }
 }
 
-fn minimize<F: Fn(i32, i32) -> i32>(crabs: &Vec<i32>, max: i32, cost: F) -> i32 {
-    let mut best = i32::MAX;
+/// The lowest total cost of moving all crabs to the same position. Costs that overflow an i64
+/// are never the lowest, unless all of them do, which is an error.
+fn minimize<F: Fn(i64, i64) -> Option<i64>>(
+    crabs: &[i32],
+    max: i32,
+    cost: F,
+) -> crate::Result<i64> {
+    let mut best = None;
 
     // Just try every value and abort fast if the value is worse than our current 'best'
-    for i in 0..=max {
-        let mut current = 0;
+    'positions: for i in 0..=max {
+        let mut current: i64 = 0;
 
         for c in crabs.chunks(8) {
-            current += c.iter().map(|&x| cost(x, i)).sum::<i32>();
+            for &x in c {
+                current = match cost(x.into(), i.into()).and_then(|c| current.checked_add(c)) {
+                    Some(current) => current,
+                    None => continue 'positions,
+                };
+            }
 
-            if current >= best {
-                break;
+            if best.is_some_and(|best| current >= best) {
+                continue 'positions;
             }
         }
 
-        if current < best {
-            best = current;
-        }
+        best = Some(current);
     }
 
-    best
+    best.ok_or(crate::Error::InvalidInput)
 }
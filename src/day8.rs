@@ -1,14 +1,18 @@
 use std::collections::{HashMap, HashSet};
 
-const INPUT: &str = include_str!("../problems/problem8");
+use crate::{
+    error::end_of,
+    solution::{Answer, Solution},
+};
+
 const N: usize = 7;
 const CHARS: [char; N] = ['a', 'b', 'c', 'd', 'e', 'f', 'g'];
 const DIGIT_PATTERNS: [&str; 10] = [
     "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
 ];
 
-type Permutation = [u8; N];
-type Pattern = [u8; N];
+pub type Permutation = [u8; N];
+pub type Pattern = [u8; N];
 
 pub fn parse_pattern(s: &str) -> Pattern {
     let mut ret = [0; N];
@@ -32,94 +36,122 @@ pub fn permute(pattern: &Pattern, pi: &Permutation) -> Pattern {
     ret
 }
 
-pub fn solve() -> crate::Result<()> {
-    let observations = INPUT
-        .lines()
-        .map(|line| {
-            let mut split = line.split(" | ").map(|part| {
-                part.split_whitespace()
-                    .map(|pat| parse_pattern(pat))
-                    .collect::<Vec<_>>()
-            });
-
-            (split.next().unwrap(), split.next().unwrap())
-        })
-        .collect::<Vec<_>>();
-
-    // Determine a permutation pi such that for every input binary pattern
-    // (x6, ..., x0) it holds that (x_pi^-1(6), ..., x^pi^-1(0)) is a valid pattern
-    fn make_pi<F: Fn(&Pattern) -> bool>(
-        mut pi: Permutation,
-        i: usize,
-        used: &mut HashSet<u8>,
-        inp: &Vec<Pattern>,
-        valid_pattern: &F,
-    ) -> Option<Permutation> {
-        if i == N {
-            if used.len() < N {
-                return None;
-            }
+// Determine a permutation pi such that for every input binary pattern
+// (x6, ..., x0) it holds that (x_pi^-1(6), ..., x^pi^-1(0)) is a valid pattern
+fn make_pi<F: Fn(&Pattern) -> bool>(
+    mut pi: Permutation,
+    i: usize,
+    used: &mut HashSet<u8>,
+    inp: &[Pattern],
+    valid_pattern: &F,
+) -> Option<Permutation> {
+    if i == N {
+        if used.len() < N {
+            return None;
+        }
 
-            for pattern in inp {
-                let permuted = permute(pattern, &pi);
+        for pattern in inp {
+            let permuted = permute(pattern, &pi);
 
-                if !valid_pattern(&permuted) {
-                    return None;
-                }
+            if !valid_pattern(&permuted) {
+                return None;
             }
+        }
 
-            Some(pi)
-        } else {
-            for j in 0..(N as u8) {
-                if used.contains(&j) {
-                    continue;
-                }
-
-                used.insert(j);
-                pi[i] = j;
+        Some(pi)
+    } else {
+        for j in 0..(N as u8) {
+            if used.contains(&j) {
+                continue;
+            }
 
-                if let Some(pi) = make_pi(pi, i + 1, used, inp, valid_pattern) {
-                    return Some(pi);
-                }
+            used.insert(j);
+            pi[i] = j;
 
-                used.remove(&j);
+            if let Some(pi) = make_pi(pi, i + 1, used, inp, valid_pattern) {
+                return Some(pi);
             }
 
-            None
+            used.remove(&j);
         }
+
+        None
     }
+}
+
+/// The observed input patterns and the output patterns of one display
+pub type Observation = (Vec<Pattern>, Vec<Pattern>);
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Observation>;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let parse_patterns = |part: &str| {
+            part.split_whitespace()
+                .map(|word| match word.find(|c| !CHARS.contains(&c)) {
+                    Some(i) => Err(crate::Error::parse(input, &word[i..], "segments a to g")),
+                    None => Ok(parse_pattern(word)),
+                })
+                .collect::<crate::Result<Vec<_>>>()
+        };
+
+        input
+            .lines()
+            .map(|line| {
+                let (inp, outp) = line.split_once(" | ").ok_or_else(|| {
+                    crate::Error::parse(input, end_of(line), "patterns | output digits")
+                })?;
+
+                Ok((parse_patterns(inp)?, parse_patterns(outp)?))
+            })
+            .collect()
+    }
+
+    fn part1(observations: &Self::Input) -> crate::Result<Answer> {
+        let mut prob1: u64 = 0;
 
-    let digit_patterns: HashMap<Pattern, u8> = DIGIT_PATTERNS
-        .iter()
-        .enumerate()
-        .map(|(i, s)| (parse_pattern(s), i as u8))
-        .collect();
-
-    let mut prob1 = 0;
-    let mut prob2 = 0;
-
-    for (inp, outp) in observations {
-        let pi = make_pi([0; N], 0, &mut Default::default(), &inp, &|p: &Pattern| {
-            digit_patterns.contains_key(p)
-        })
-        .ok_or(crate::Error::InvalidInput)?;
-
-        let mut tmp: u64 = 0;
-        for w in outp {
-            tmp *= 10;
-            tmp += digit_patterns[&permute(&w, &pi)] as u64;
-
-            match w.iter().sum() {
-                2 | 3 | 4 | 7 => prob1 += 1,
-                _ => {}
+        for (_, outp) in observations {
+            for w in outp {
+                match w.iter().sum() {
+                    2 | 3 | 4 | 7 => prob1 += 1,
+                    _ => {}
+                }
             }
         }
 
-        prob2 += tmp;
+        Ok(prob1.into())
     }
 
-    println!("Problem 1: {}", prob1);
-    println!("Problem 2: {}", prob2);
+    fn part2(observations: &Self::Input) -> crate::Result<Answer> {
+        let digit_patterns: HashMap<Pattern, u8> = DIGIT_PATTERNS
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (parse_pattern(s), i as u8))
+            .collect();
+
+        let mut prob2 = 0;
+
+        for (inp, outp) in observations {
+            let pi = make_pi([0; N], 0, &mut Default::default(), inp, &|p: &Pattern| {
+                digit_patterns.contains_key(p)
+            })
+            .ok_or(crate::Error::NoSolution)?;
+
+            let mut tmp: u64 = 0;
+            for w in outp {
+                let digit = digit_patterns
+                    .get(&permute(w, &pi))
+                    .ok_or(crate::Error::NoSolution)?;
+
+                tmp *= 10;
+                tmp += *digit as u64;
+            }
 
-    Ok(())
+            prob2 += tmp;
+        }
+
+        Ok(prob2.into())
+    }
 }
@@ -1,101 +1,70 @@
-use std::collections::HashSet;
+use crate::{
+    solution::{Answer, Solution},
+    util::{Grid, Neighborhood, UnweightedGraph},
+};
 
-const INPUT: &str = include_str!("../problems/problem9");
+pub struct Heightmap {
+    grid: Grid<u8>,
+}
 
-pub fn adjacent_locations(
-    x: usize,
-    y: usize,
-    width: usize,
-    height: usize,
-) -> impl Iterator<Item = (usize, usize)> {
-    [(-1, 0), (1, 0), (0, -1), (0, 1)]
-        .iter()
-        .filter_map(move |&(dx, dy)| {
-            let (nx, ny) = ((x as isize) + dx, (y as isize) + dy);
+impl Heightmap {
+    fn low_points(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let grid = &self.grid;
 
-            if nx >= 0 && ny >= 0 && nx < width as isize && ny < height as isize {
-                Some((nx as usize, ny as usize))
-            } else {
-                None
-            }
+        grid.positions().filter(move |&pos| {
+            grid.neighbors(pos, Neighborhood::Four)
+                .all(|npos| grid[npos] > grid[pos])
         })
+    }
 }
 
-pub fn solve() -> crate::Result<()> {
-    let lines = INPUT.lines();
-
-    let mut grid: Vec<u8> = Vec::new();
-    let mut width = 0;
-
-    for line in lines {
-        let parsed = line
-            .trim()
-            .chars()
-            .map(|c| char::to_digit(c, 10).unwrap() as u8);
-
-        grid.extend(parsed);
+// Edges lead uphill to locations below height 9, so a basin is everything that can be reached
+// from its low point
+impl UnweightedGraph<(usize, usize)> for Heightmap {
+    fn adjacent(&self, &pos: &(usize, usize), out: &mut Vec<(usize, usize)>) {
+        let grid = &self.grid;
 
-        if width == 0 {
-            width = grid.len();
-        }
+        out.extend(
+            grid.neighbors(pos, Neighborhood::Four)
+                .filter(|&npos| grid[npos] < 9 && grid[npos] > grid[pos]),
+        );
     }
+}
 
-    let height = grid.len() / width;
-
-    let mut prob1: u64 = 0;
-
-    let mut basin_sizes: Vec<usize> = Default::default();
-
-    for x in 0..width {
-        for y in 0..height {
-            let mut lowpoint = true;
-            let val = grid[x + y * width];
-
-            for (nx, ny) in adjacent_locations(x, y, width, height) {
-                if grid[nx as usize + ny as usize * width] <= val {
-                    lowpoint = false;
-                    break;
-                }
-            }
-
-            if !lowpoint {
-                continue;
-            }
-
-            prob1 += val as u64 + 1;
-
-            let mut basin: HashSet<(usize, usize)> = Default::default();
-            let mut to_be_checked: Vec<(usize, usize)> = vec![(x, y)];
-
-            while let Some((x, y)) = to_be_checked.pop() {
-                basin.insert((x, y));
-
-                let val = grid[x + y * width];
+pub struct Day9;
 
-                for (nx, ny) in adjacent_locations(x, y, width, height) {
-                    let nval = grid[nx + ny * width];
+impl Solution for Day9 {
+    type Input = Heightmap;
 
-                    if nval < 9 && nval > val && !basin.contains(&(nx, ny)) {
-                        to_be_checked.push((nx, ny));
-                    }
-                }
-            }
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let grid = Grid::parse(input, "a digit", |c| c.to_digit(10).map(|d| d as u8))?;
 
-            basin_sizes.push(basin.len());
-        }
+        Ok(Heightmap { grid })
     }
 
-    println!("Problem 1: {}", prob1);
-
-    basin_sizes.sort();
-    let prob2: u64 = basin_sizes
-        .iter()
-        .rev()
-        .take(3)
-        .map(|x| *x as u64)
-        .product();
+    fn part1(map: &Self::Input) -> crate::Result<Answer> {
+        let prob1: u64 = map
+            .low_points()
+            .map(|pos| map.grid[pos] as u64 + 1)
+            .sum();
 
-    println!("Problem 2: {}", prob2);
+        Ok(prob1.into())
+    }
 
-    Ok(())
+    fn part2(map: &Self::Input) -> crate::Result<Answer> {
+        let mut basin_sizes: Vec<usize> = map
+            .low_points()
+            .map(|low_point| map.dfs(low_point).len())
+            .collect();
+
+        basin_sizes.sort();
+        let prob2: u64 = basin_sizes
+            .iter()
+            .rev()
+            .take(3)
+            .map(|x| *x as u64)
+            .product();
+
+        Ok(prob2.into())
+    }
 }
@@ -0,0 +1,162 @@
+use std::{fmt::Display, io, num::ParseIntError, str::FromStr};
+
+#[non_exhaustive]
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug)]
+pub enum Error {
+    IOError(io::Error),
+    ParseIntError(ParseIntError),
+    /// Malformed puzzle input, with the position of the problem
+    Parse(ParseError),
+    NoInput,
+    InvalidInput,
+    /// The input could be parsed, but the puzzle has no answer for it
+    NoSolution,
+    /// The day has no solver variant with this name
+    UnknownVariant(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// A parse error for `token`, which has to be a slice of `input`
+    pub fn parse(input: &str, token: &str, expected: impl Into<String>) -> Self {
+        Error::Parse(ParseError::at(input, token, expected))
+    }
+
+    /// Set the line of a parse error that was reported relative to a single line of the
+    /// input, all other errors are returned unchanged
+    pub fn on_line(self, line: usize) -> Self {
+        match self {
+            Error::Parse(e) if e.line > 0 => Error::Parse(ParseError { line, ..e }),
+            e => e,
+        }
+    }
+
+    /// Attach the day to parse errors, all other errors are returned unchanged
+    pub fn in_day(self, day: u8) -> Self {
+        match self {
+            Error::Parse(e) => Error::Parse(ParseError { day: Some(day), ..e }),
+            e => e,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(e) => e.fmt(f),
+            _ => f.write_fmt(format_args!("{:?}", self)),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(val: io::Error) -> Self {
+        Error::IOError(val)
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(inner: ParseIntError) -> Self {
+        Error::ParseIntError(inner)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(inner: ParseError) -> Self {
+        Error::Parse(inner)
+    }
+}
+
+/// Where and why the input of a day could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Filled in by the runner, parsers do not know which day they belong to
+    pub day: Option<u8>,
+    /// 1-based, 0 if the position is unknown
+    pub line: usize,
+    /// 1-based and counted in characters, 0 if the position is unknown
+    pub column: usize,
+    /// The offending text, empty if something was missing
+    pub found: String,
+    /// Description of what should have been there instead
+    pub expected: String,
+}
+
+impl ParseError {
+    /// A parse error for `token`. The line and column are derived from where `token` lies
+    /// in `input`, so `token` has to be a slice of `input`. An empty slice at the end of a
+    /// line can be used to report something missing.
+    pub fn at(input: &str, token: &str, expected: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+
+        let (line, column) = if offset <= input.len() && input.is_char_boundary(offset) {
+            let before = &input[..offset];
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+            (
+                before.matches('\n').count() + 1,
+                before[line_start..].chars().count() + 1,
+            )
+        } else {
+            (0, 0)
+        };
+
+        ParseError {
+            day: None,
+            line,
+            column,
+            found: token.to_owned(),
+            expected: expected.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}, ", day)?;
+        }
+
+        if self.line > 0 {
+            write!(f, "line {}, column {}: ", self.line, self.column)?;
+        }
+
+        write!(f, "expected {}, ", self.expected)?;
+
+        if self.found.is_empty() {
+            write!(f, "found nothing")
+        } else {
+            write!(f, "found {:?}", self.found)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse `token` as a number, reporting its position in `input` if it is not one
+pub fn parse_number<T: FromStr>(input: &str, token: &str) -> Result<T> {
+    token
+        .parse::<T>()
+        .map_err(|_| Error::parse(input, token, "a number"))
+}
+
+/// Result of the parsers in `util::parse` and those built from them: on success the parsed value
+/// and the remaining input, on failure the remaining input at the point of failure and a
+/// description of what was expected there
+pub type Parsed<'a, T> = std::result::Result<(T, &'a str), (&'a str, &'static str)>;
+
+/// Turn the failure of a `Parsed` into a parse error pointing at the first character that
+/// could not be parsed
+pub fn failed_at(input: &str, (at, expected): (&str, &'static str)) -> Error {
+    let len = at.chars().next().map_or(0, char::len_utf8);
+    Error::parse(input, &at[..len], expected)
+}
+
+/// The empty slice at the end of `s`, for reporting something missing after it
+pub fn end_of(s: &str) -> &str {
+    &s[s.len()..]
+}
@@ -0,0 +1,998 @@
+//! Random puzzle inputs for stress testing. Every day has a generator that produces an input
+//! of about the size and shape of the real one from a seed, so the same seed always gives the
+//! same input. Some generators build the input backwards from its solution and therefore
+//! also know the answers.
+
+use std::{fmt::Write, ops::RangeInclusive};
+
+use crate::{
+    solution::Answer,
+    solvers::Part,
+    util::{BoundingBox, Rotation, Vec3, Vector},
+};
+
+/// A small, fast pseudo random number generator (SplitMix64). Not suitable for anything but
+/// test data.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+
+        z ^ (z >> 31)
+    }
+
+    /// A number from 0 to n - 1. Panics if n is zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+
+        // The bias of the modulo is negligible for the small n used here
+        self.next_u64() % n
+    }
+
+    /// A number from the range, both ends inclusive
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = (*range.start(), *range.end());
+        assert!(start <= end, "empty range");
+
+        start + self.below((end - start) as u64 + 1) as i64
+    }
+
+    /// True with the given probability
+    pub fn chance(&mut self, p: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= p
+    }
+
+    /// A random element of the slice. Panics if it is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+/// A generated input, together with the answers that are known from how it was generated
+#[derive(Debug, Clone)]
+pub struct Generated {
+    pub input: String,
+    pub answers: Vec<(Part, Answer)>,
+}
+
+impl From<String> for Generated {
+    fn from(input: String) -> Self {
+        Generated {
+            input,
+            answers: Vec::new(),
+        }
+    }
+}
+
+/// Generate an input for the given day. Different days get different inputs from the same
+/// seed. None if there is no such day.
+pub fn generate(day: u8, seed: u64) -> Option<Generated> {
+    // Mix the day into the seed, so that days do not share their random numbers
+    let mut rng = Rng::new(seed ^ (day as u64).wrapping_mul(0x2545f4914f6cdd1d));
+    let rng = &mut rng;
+
+    let generated = match day {
+        1 => day1(rng).into(),
+        2 => day2(rng).into(),
+        3 => day3(rng).into(),
+        4 => day4(rng).into(),
+        5 => day5(rng).into(),
+        6 => day6(rng).into(),
+        7 => day7(rng).into(),
+        8 => day8(rng).into(),
+        9 => day9(rng).into(),
+        10 => day10(rng).into(),
+        11 => day11(rng),
+        12 => day12(rng).into(),
+        13 => day13(rng),
+        14 => day14(rng).into(),
+        15 => digit_grid(rng, 100, 100, 1..=9).into(),
+        16 => day16(rng),
+        17 => day17(rng).into(),
+        18 => day18(rng).into(),
+        19 => day19(rng),
+        20 => day20(rng).into(),
+        21 => day21(rng).into(),
+        22 => day22(rng).into(),
+        23 => day23(rng).into(),
+        24 => day24(rng),
+        25 => day25(rng).into(),
+        _ => return None,
+    };
+
+    Some(generated)
+}
+
+/// Lines of the given items
+fn lines<T: std::fmt::Display>(items: impl IntoIterator<Item = T>) -> String {
+    items.into_iter().fold(String::new(), |mut s, item| {
+        let _ = writeln!(s, "{}", item);
+        s
+    })
+}
+
+fn comma_separated<T: ToString>(items: impl IntoIterator<Item = T>) -> String {
+    items
+        .into_iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn digit_grid(rng: &mut Rng, width: usize, height: usize, digits: RangeInclusive<i64>) -> String {
+    let rows = (0..height).map(|_| {
+        (0..width)
+            .map(|_| char::from(b'0' + rng.range(digits.clone()) as u8))
+            .collect::<String>()
+    });
+
+    lines(rows.collect::<Vec<_>>())
+}
+
+/// A slowly rising sea floor
+fn day1(rng: &mut Rng) -> String {
+    let mut depth = rng.range(100..=200);
+
+    let depths = (0..2000).map(|_| {
+        depth = (depth + rng.range(-5..=10)).max(0);
+        depth
+    });
+
+    lines(depths.collect::<Vec<_>>())
+}
+
+/// Commands that never take the submarine (or its aim) above the surface
+fn day2(rng: &mut Rng) -> String {
+    let mut aim = 0;
+
+    let commands = (0..1000).map(|_| {
+        let x = rng.range(1..=9);
+        let command = match *rng.choose(&["forward", "down", "up"]) {
+            "up" if aim < x => "down",
+            command => command,
+        };
+
+        match command {
+            "down" => aim += x,
+            "up" => aim -= x,
+            _ => {}
+        }
+
+        format!("{} {}", command, x)
+    });
+
+    lines(commands.collect::<Vec<_>>())
+}
+
+/// Distinct 12 bit numbers, so the ratings of part 2 are well defined
+fn day3(rng: &mut Rng) -> String {
+    let mut numbers: Vec<u32> = (0..1 << 12).collect();
+    rng.shuffle(&mut numbers);
+
+    lines(numbers[..1000].iter().map(|x| format!("{:012b}", x)))
+}
+
+/// All numbers up to 99 are drawn, so every board wins at some point
+fn day4(rng: &mut Rng) -> String {
+    let mut numbers: Vec<u32> = (0..100).collect();
+
+    rng.shuffle(&mut numbers);
+    let mut input = comma_separated(&numbers) + "\n";
+
+    for _ in 0..100 {
+        rng.shuffle(&mut numbers);
+
+        input += "\n";
+        for row in numbers[..25].chunks(5) {
+            let row: Vec<_> = row.iter().map(|x| format!("{:>2}", x)).collect();
+            input += &row.join(" ");
+            input += "\n";
+        }
+    }
+
+    input
+}
+
+/// Horizontal, vertical and diagonal lines
+fn day5(rng: &mut Rng) -> String {
+    let vents = (0..500).map(|_| {
+        let (x1, y1) = (rng.range(0..=989), rng.range(0..=989));
+        let length = rng.range(1..=300);
+
+        let (x2, y2) = match rng.below(4) {
+            0 => ((x1 + length).min(989), y1),
+            1 => (x1, (y1 + length).min(989)),
+            2 => {
+                let length = length.min(989 - x1).min(989 - y1);
+                (x1 + length, y1 + length)
+            }
+            _ => {
+                let length = length.min(989 - x1).min(y1);
+                (x1 + length, y1 - length)
+            }
+        };
+
+        // Lines are given in either direction
+        if rng.chance(0.5) {
+            format!("{},{} -> {},{}", x1, y1, x2, y2)
+        } else {
+            format!("{},{} -> {},{}", x2, y2, x1, y1)
+        }
+    });
+
+    lines(vents.collect::<Vec<_>>())
+}
+
+fn day6(rng: &mut Rng) -> String {
+    let fish: Vec<_> = (0..300).map(|_| rng.range(1..=5)).collect();
+
+    comma_separated(fish) + "\n"
+}
+
+fn day7(rng: &mut Rng) -> String {
+    let crabs: Vec<_> = (0..1000).map(|_| rng.range(0..=2000)).collect();
+
+    comma_separated(crabs) + "\n"
+}
+
+/// Displays with randomly wired segments, showing all ten digits in a random order followed by
+/// four digits to decode
+fn day8(rng: &mut Rng) -> String {
+    const DIGITS: [&str; 10] = [
+        "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+    ];
+
+    let entries = (0..200).map(|_| {
+        let mut wiring: Vec<char> = "abcdefg".chars().collect();
+        rng.shuffle(&mut wiring);
+
+        let pattern = |rng: &mut Rng, digit: usize| {
+            let mut segments: Vec<char> = DIGITS[digit]
+                .bytes()
+                .map(|s| wiring[(s - b'a') as usize])
+                .collect();
+            rng.shuffle(&mut segments);
+
+            segments.into_iter().collect::<String>()
+        };
+
+        let mut order: Vec<usize> = (0..10).collect();
+        rng.shuffle(&mut order);
+
+        let patterns: Vec<_> = order.iter().map(|&d| pattern(rng, d)).collect();
+        let output: Vec<_> = (0..4)
+            .map(|_| {
+                let digit = rng.below(10) as usize;
+                pattern(rng, digit)
+            })
+            .collect();
+
+        format!("{} | {}", patterns.join(" "), output.join(" "))
+    });
+
+    lines(entries.collect::<Vec<_>>())
+}
+
+/// Heights where about every third location is a 9, which splits the map into basins
+fn day9(rng: &mut Rng) -> String {
+    let rows = (0..100).map(|_| {
+        (0..100)
+            .map(|_| {
+                if rng.chance(0.3) {
+                    '9'
+                } else {
+                    char::from(b'0' + rng.below(9) as u8)
+                }
+            })
+            .collect::<String>()
+    });
+
+    lines(rows.collect::<Vec<_>>())
+}
+
+/// Lines that are either corrupted or incomplete
+fn day10(rng: &mut Rng) -> String {
+    const PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+    let navigation = (0..100).map(|_| {
+        let corrupted = rng.chance(0.5);
+        let length = rng.range(20..=100);
+
+        let mut line = String::new();
+        let mut open = Vec::new();
+
+        for i in 0..length {
+            if corrupted && i == length / 2 && !open.is_empty() {
+                // Close the last chunk with the wrong character
+                let expected = open.pop().unwrap();
+                let wrong = loop {
+                    let (_, close) = *rng.choose(&PAIRS);
+                    if close != expected {
+                        break close;
+                    }
+                };
+
+                line.push(wrong);
+            } else if open.is_empty() || rng.chance(0.55) {
+                let (open_char, close) = *rng.choose(&PAIRS);
+
+                line.push(open_char);
+                open.push(close);
+            } else {
+                line.push(open.pop().unwrap());
+            }
+        }
+
+        // Incomplete lines have to leave a chunk open
+        if open.is_empty() {
+            line.push(rng.choose(&PAIRS).0);
+        }
+
+        line
+    });
+
+    lines(navigation.collect::<Vec<_>>())
+}
+
+/// Energy levels of octopuses that flash all at once at some point. Random grids usually get
+/// there within a few hundred steps, which a simulation of their own checks, so a few grids
+/// are drawn. Should none of them get there, all octopuses start with the same energy level,
+/// which makes them flash together from their first flash on.
+fn day11(rng: &mut Rng) -> Generated {
+    const SIZE: usize = 10;
+
+    for _ in 0..100 {
+        let levels: Vec<u8> = (0..SIZE * SIZE).map(|_| rng.below(10) as u8).collect();
+
+        if let (flashes, Some(synchronized)) = flash_octopuses(&levels, SIZE) {
+            return octopus_grid(&levels, SIZE, flashes, synchronized);
+        }
+    }
+
+    // All flash every 10 steps, the first time after 10 - level steps: 10 times in 100 steps
+    let level = rng.below(10) as u8;
+    let levels = vec![level; SIZE * SIZE];
+
+    octopus_grid(&levels, SIZE, 10 * (SIZE * SIZE) as u64, 10 - level as u64)
+}
+
+/// Simulate a square grid of octopuses. Returns the number of flashes in the first 100 steps,
+/// and the first step in which all of them flash, if that happens within 1000 steps.
+fn flash_octopuses(levels: &[u8], size: usize) -> (u64, Option<u64>) {
+    let mut levels = levels.to_vec();
+    let mut flashes = 0;
+    let mut synchronized = None;
+
+    for step in 1..=1000 {
+        let mut flashing: Vec<usize> = Vec::new();
+
+        for (i, level) in levels.iter_mut().enumerate() {
+            *level += 1;
+
+            if *level == 10 {
+                flashing.push(i);
+            }
+        }
+
+        let mut flashed = 0;
+
+        while let Some(i) = flashing.pop() {
+            flashed += 1;
+
+            let (x, y) = (i % size, i / size);
+
+            for ny in y.saturating_sub(1)..=(y + 1).min(size - 1) {
+                for nx in x.saturating_sub(1)..=(x + 1).min(size - 1) {
+                    let level = &mut levels[nx + ny * size];
+                    *level += 1;
+
+                    // Only the increment to 10 lets an octopus flash, the one that flashes is
+                    // past that already
+                    if *level == 10 {
+                        flashing.push(nx + ny * size);
+                    }
+                }
+            }
+        }
+
+        for level in levels.iter_mut().filter(|level| **level > 9) {
+            *level = 0;
+        }
+
+        if step <= 100 {
+            flashes += flashed;
+        }
+
+        if flashed == levels.len() as u64 {
+            synchronized = synchronized.or(Some(step));
+        }
+
+        if step >= 100 && synchronized.is_some() {
+            break;
+        }
+    }
+
+    (flashes, synchronized)
+}
+
+fn octopus_grid(levels: &[u8], size: usize, flashes: u64, synchronized: u64) -> Generated {
+    let rows = levels.chunks(size).map(|row| {
+        row.iter()
+            .map(|level| char::from(b'0' + level))
+            .collect::<String>()
+    });
+
+    Generated {
+        input: lines(rows),
+        answers: vec![
+            (Part::One, flashes.into()),
+            (Part::Two, synchronized.into()),
+        ],
+    }
+}
+
+/// A cave system where big caves are only connected to small ones, since two connected big
+/// caves would allow infinitely many paths
+fn day12(rng: &mut Rng) -> String {
+    let names = |rng: &mut Rng, count: usize, base: u8| {
+        let mut names: Vec<String> = Vec::new();
+
+        while names.len() < count {
+            let name: String = (0..2)
+                .map(|_| char::from(base + rng.below(26) as u8))
+                .collect();
+
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        names
+    };
+
+    let small = names(rng, 8, b'a');
+    let big = names(rng, 3, b'A');
+    let caves: Vec<&str> = small.iter().chain(&big).map(|c| c.as_str()).collect();
+
+    let mut edges: Vec<(&str, &str)> = Vec::new();
+
+    for &cave in &caves {
+        // Big caves are not connected to each other
+        let count = if cave.len() == 2 && cave.as_bytes()[0].is_ascii_uppercase() {
+            rng.range(2..=3)
+        } else {
+            rng.range(1..=2)
+        };
+
+        for _ in 0..count {
+            edges.push((cave, rng.choose(&small).as_str()));
+        }
+    }
+
+    for _ in 0..2 {
+        edges.push(("start", *rng.choose(&caves)));
+        edges.push((*rng.choose(&caves), "end"));
+    }
+
+    // No loops and every connection only once
+    let mut seen = Vec::new();
+    edges.retain(|&(a, b)| {
+        let new = a != b && !seen.contains(&(b, a)) && !seen.contains(&(a, b));
+        seen.push((a, b));
+        new
+    });
+
+    lines(edges.iter().map(|(a, b)| format!("{}-{}", a, b)))
+}
+
+/// Dots that fold into a random 40 x 6 pattern. The paper is generated by unfolding the
+/// pattern, with every dot ending up on one or both sides of each fold.
+fn day13(rng: &mut Rng) -> Generated {
+    // Folding in half, so that no dot lies on a fold line
+    const FOLDS: [(char, i64); 12] = [
+        ('x', 655),
+        ('y', 447),
+        ('x', 327),
+        ('y', 223),
+        ('x', 163),
+        ('y', 111),
+        ('x', 81),
+        ('y', 55),
+        ('x', 40),
+        ('y', 27),
+        ('y', 13),
+        ('y', 6),
+    ];
+
+    let mut dots: Vec<(i64, i64)> = (0..6)
+        .flat_map(|y| (0..40).map(move |x| (x, y)))
+        .filter(|_| rng.chance(0.4))
+        .collect();
+
+    let pattern: Vec<String> = (0..6)
+        .map(|y| {
+            (0..40)
+                .map(|x| if dots.contains(&(x, y)) { '#' } else { '.' })
+                .collect()
+        })
+        .collect();
+
+    let mut after_first_fold = 0;
+
+    for (i, &(axis, line)) in FOLDS.iter().enumerate().rev() {
+        if i == 0 {
+            after_first_fold = dots.len();
+        }
+
+        let mut unfolded = Vec::new();
+
+        for &(x, y) in &dots {
+            let mirrored = if axis == 'x' {
+                (2 * line - x, y)
+            } else {
+                (x, 2 * line - y)
+            };
+
+            match rng.below(3) {
+                0 => unfolded.push((x, y)),
+                1 => unfolded.push(mirrored),
+                _ => unfolded.extend([(x, y), mirrored]),
+            }
+        }
+
+        dots = unfolded;
+    }
+
+    rng.shuffle(&mut dots);
+
+    let mut input = lines(dots.iter().map(|(x, y)| format!("{},{}", x, y)));
+    input += "\n";
+    input += &lines(
+        FOLDS
+            .iter()
+            .map(|(axis, line)| format!("fold along {}={}", axis, line)),
+    );
+
+    Generated {
+        input,
+        answers: vec![
+            (Part::One, after_first_fold.into()),
+            (Part::Two, Answer::Grid(pattern)),
+        ],
+    }
+}
+
+/// A template and a rule for every pair of ten elements
+fn day14(rng: &mut Rng) -> String {
+    let elements: Vec<char> = "BCFHKNOPSV".chars().collect();
+
+    let template: String = (0..20).map(|_| *rng.choose(&elements)).collect();
+
+    let mut input = template + "\n\n";
+    for &a in &elements {
+        for &b in &elements {
+            let _ = writeln!(input, "{}{} -> {}", a, b, rng.choose(&elements));
+        }
+    }
+
+    input
+}
+
+/// A random packet, its bits, version sum and value
+fn packet(rng: &mut Rng, depth: u32) -> (String, u64, u64) {
+    let version = rng.below(8);
+    let mut bits = format!("{:03b}", version);
+
+    if depth >= 4 || rng.chance(0.3) {
+        let value = rng.below(1000);
+        let digits = format!("{:b}", value);
+
+        // Groups of four bits, each prefixed with whether another one follows
+        let padded = format!("{:0>width$}", digits, width = digits.len().div_ceil(4) * 4);
+        let groups: Vec<&str> = (0..padded.len() / 4)
+            .map(|i| &padded[4 * i..4 * i + 4])
+            .collect();
+
+        bits += "100";
+        for (i, group) in groups.iter().enumerate() {
+            bits += if i + 1 < groups.len() { "1" } else { "0" };
+            bits += group;
+        }
+
+        return (bits, version, value);
+    }
+
+    let type_id = rng.below(7);
+    let type_id = if type_id >= 4 { type_id + 1 } else { type_id };
+
+    // Comparisons have exactly two subpackets
+    let count = if type_id >= 5 { 2 } else { rng.range(1..=3) };
+
+    let subpackets: Vec<_> = (0..count).map(|_| packet(rng, depth + 1)).collect();
+    let values = subpackets.iter().map(|&(_, _, value)| value);
+
+    let value = match type_id {
+        0 => values.sum(),
+        // Products of subpackets that are too big would overflow, so they become sums
+        1 => values
+            .clone()
+            .try_fold(1u64, |p, v| p.checked_mul(v).filter(|&p| p < 1 << 40))
+            .unwrap_or_else(|| values.sum()),
+        2 => values.min().unwrap(),
+        3 => values.max().unwrap(),
+        _ => {
+            let (a, b) = (subpackets[0].2, subpackets[1].2);
+            (match type_id {
+                5 => a > b,
+                6 => a < b,
+                _ => a == b,
+            }) as u64
+        }
+    };
+
+    // A product that overflowed is a sum
+    let type_id = if type_id == 1 && value != subpackets.iter().map(|s| s.2).product::<u64>() {
+        0
+    } else {
+        type_id
+    };
+
+    let contents: String = subpackets
+        .iter()
+        .map(|(bits, _, _)| bits.as_str())
+        .collect();
+    let version_sum = version + subpackets.iter().map(|&(_, v, _)| v).sum::<u64>();
+
+    bits += &format!("{:03b}", type_id);
+    if rng.chance(0.5) {
+        bits += &format!("0{:015b}", contents.len());
+    } else {
+        bits += &format!("1{:011b}", count);
+    }
+    bits += &contents;
+
+    (bits, version_sum, value)
+}
+
+/// An operator packet, encoded in hexadecimal
+fn day16(rng: &mut Rng) -> Generated {
+    let (mut bits, version_sum, value) = loop {
+        let generated = packet(rng, 0);
+
+        // The outermost packet should be an operator
+        if &generated.0[3..6] != "100" {
+            break generated;
+        }
+    };
+
+    while bits.len() % 4 != 0 {
+        bits.push('0');
+    }
+
+    let hex: String = (0..bits.len() / 4)
+        .map(|i| {
+            let nibble = u32::from_str_radix(&bits[4 * i..4 * i + 4], 2).unwrap();
+            char::from_digit(nibble, 16).unwrap().to_ascii_uppercase()
+        })
+        .collect();
+
+    Generated {
+        input: hex + "\n",
+        answers: vec![(Part::One, version_sum.into()), (Part::Two, value.into())],
+    }
+}
+
+/// A target area to the right of and below the launcher
+fn day17(rng: &mut Rng) -> String {
+    let x = rng.range(20..=250);
+    let y = rng.range(-150..=-50);
+
+    format!(
+        "target area: x={}..{}, y={}..{}\n",
+        x,
+        x + rng.range(10..=50),
+        y,
+        y + rng.range(10..=40)
+    )
+}
+
+/// A reduced snailfish number with pairs nested at most four deep
+fn snailfish_number(rng: &mut Rng, depth: u32) -> String {
+    if depth == 0 || (depth < 4 && rng.chance(0.6)) {
+        let left = snailfish_number(rng, depth + 1);
+        let right = snailfish_number(rng, depth + 1);
+
+        format!("[{},{}]", left, right)
+    } else {
+        rng.range(0..=9).to_string()
+    }
+}
+
+fn day18(rng: &mut Rng) -> String {
+    lines(
+        (0..100)
+            .map(|_| snailfish_number(rng, 0))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Scanners along a random walk, each in a random orientation. Every scanner shares at least
+/// 12 beacons with the one before it, so the scanners can be aligned.
+fn day19(rng: &mut Rng) -> Generated {
+    const RANGE: i32 = 1000;
+
+    let mut position = Vec3::ZERO;
+    let mut positions = vec![position];
+    let mut beacons: Vec<Vec3<i32>> = Vec::new();
+
+    let coordinate = |rng: &mut Rng, range: RangeInclusive<i32>| {
+        rng.range(*range.start() as i64..=*range.end() as i64) as i32
+    };
+
+    for i in 0..12 {
+        // Beacons only this scanner sees (or others by chance)
+        for _ in 0..rng.range(5..=15) {
+            let offset = Vec3::new(
+                coordinate(rng, -RANGE..=RANGE),
+                coordinate(rng, -RANGE..=RANGE),
+                coordinate(rng, -RANGE..=RANGE),
+            );
+            beacons.push(position + offset);
+        }
+
+        if i == 11 {
+            break;
+        }
+
+        let step = Vec3::new(
+            coordinate(rng, -1100..=1100),
+            coordinate(rng, -400..=400),
+            coordinate(rng, -400..=400),
+        );
+        let next = position + step;
+
+        // The region both scanners see. Component-wise, the derived `Ord` would compare
+        // the vectors lexicographically.
+        let min = Vector::max(position, next) - Vec3::splat(RANGE);
+        let max = Vector::min(position, next) + Vec3::splat(RANGE);
+
+        for _ in 0..rng.range(12..=15) {
+            beacons.push(Vec3::new(
+                coordinate(rng, min.x..=max.x),
+                coordinate(rng, min.y..=max.y),
+                coordinate(rng, min.z..=max.z),
+            ));
+        }
+
+        position = next;
+        positions.push(position);
+    }
+
+    beacons.sort();
+    beacons.dedup();
+
+    let rotations: Vec<Rotation> = Rotation::all().collect();
+    let mut input = String::new();
+
+    for (i, &scanner) in positions.iter().enumerate() {
+        // Scanner 0 defines the orientation of the others
+        let rotation = if i == 0 {
+            Rotation::IDENTITY
+        } else {
+            *rng.choose(&rotations)
+        };
+
+        let range = BoundingBox {
+            min: scanner - Vec3::new(RANGE, RANGE, RANGE),
+            max: scanner + Vec3::new(RANGE, RANGE, RANGE),
+        };
+
+        let mut seen: Vec<_> = beacons
+            .iter()
+            .filter(|&&b| range.contains(b))
+            .map(|&b| rotation.inverse() * (b - scanner))
+            .collect();
+        rng.shuffle(&mut seen);
+
+        if i > 0 {
+            input += "\n";
+        }
+        let _ = writeln!(input, "--- scanner {} ---", i);
+        input += &lines(seen);
+    }
+
+    let max_distance = positions
+        .iter()
+        .flat_map(|&a| positions.iter().map(move |&b| (a - b).manhattan()))
+        .max()
+        .unwrap_or(0);
+
+    Generated {
+        input,
+        answers: vec![
+            (Part::One, beacons.len().into()),
+            (Part::Two, max_distance.into()),
+        ],
+    }
+}
+
+/// An algorithm that does not light up the infinite image forever, and an image
+fn day20(rng: &mut Rng) -> String {
+    let mut algorithm: Vec<char> = (0..512)
+        .map(|_| if rng.chance(0.5) { '#' } else { '.' })
+        .collect();
+
+    // If empty surroundings light up, full ones have to go dark again
+    if algorithm[0] == '#' {
+        algorithm[511] = '.';
+    }
+
+    let mut input: String = algorithm.into_iter().collect();
+    input += "\n\n";
+
+    for _ in 0..100 {
+        input += &(0..100)
+            .map(|_| if rng.chance(0.5) { '#' } else { '.' })
+            .collect::<String>();
+        input += "\n";
+    }
+
+    input
+}
+
+fn day21(rng: &mut Rng) -> String {
+    format!(
+        "Player 1 starting position: {}\nPlayer 2 starting position: {}\n",
+        rng.range(1..=10),
+        rng.range(1..=10)
+    )
+}
+
+/// Cuboids in the initialization area followed by big ones outside of it, starting with an on
+fn day22(rng: &mut Rng) -> String {
+    let cuboid = |rng: &mut Rng, center: i64, size: i64| loop {
+        let ranges: Vec<(i64, i64)> = (0..3)
+            .map(|_| {
+                let start = rng.range(-center..=center - size);
+                (start, start + rng.range(size / 4..=size))
+            })
+            .collect();
+
+        // Like in the real inputs, only the first cuboids touch the initialization area
+        let initialization = ranges.iter().all(|&(start, end)| start <= 50 && end >= -50);
+
+        if initialization == (center <= 50) {
+            let ranges: Vec<String> = ["x", "y", "z"]
+                .iter()
+                .zip(ranges)
+                .map(|(axis, (start, end))| format!("{}={}..{}", axis, start, end))
+                .collect();
+
+            break ranges.join(",");
+        }
+    };
+
+    let instructions = (0..220).map(|i| {
+        let state = if i == 0 || rng.chance(0.6) {
+            "on"
+        } else {
+            "off"
+        };
+
+        if i < 20 {
+            format!("{} {}", state, cuboid(rng, 50, 40))
+        } else {
+            format!("{} {}", state, cuboid(rng, 90_000, 40_000))
+        }
+    });
+
+    lines(instructions.collect::<Vec<_>>())
+}
+
+/// Two amphipods of every type, in random rooms
+fn day23(rng: &mut Rng) -> String {
+    let mut amphipods: Vec<char> = "AABBCCDD".chars().collect();
+    rng.shuffle(&mut amphipods);
+
+    let row = |r: &[char]| {
+        let letters: Vec<String> = r.iter().map(|c| c.to_string()).collect();
+        letters.join("#")
+    };
+
+    format!(
+        "#############\n#...........#\n###{}###\n  #{}#\n  #########\n",
+        row(&amphipods[..4]),
+        row(&amphipods[4..])
+    )
+}
+
+/// A program of the same structure as the real ones. Each block either pushes a digit plus
+/// an offset onto a stack in z, or pops one and compares it to its digit, so the digits of
+/// a push and its pop have to differ by a fixed amount. The answers follow from these
+/// differences.
+fn day24(rng: &mut Rng) -> Generated {
+    // Pairs up the blocks like balanced parentheses
+    let mut opens = 0;
+    let mut stack = Vec::new();
+    let mut blocks = Vec::new();
+    let (mut biggest, mut smallest) = ([0i64; 14], [0i64; 14]);
+
+    for i in 0..14 {
+        let remaining = 14 - i;
+        let must_close = stack.len() == remaining;
+        let can_open = opens < 7 && !must_close;
+
+        if can_open && (stack.is_empty() || rng.chance(0.5)) {
+            let offset = rng.range(1..=16);
+
+            opens += 1;
+            stack.push((i, offset));
+            blocks.push((1, rng.range(10..=16), offset));
+        } else {
+            let (j, offset) = stack.pop().unwrap();
+
+            // Digit i has to be digit j + difference
+            let difference = rng.range(-8..=8);
+            blocks.push((26, difference - offset, rng.range(1..=16)));
+
+            biggest[j] = 9.min(9 - difference);
+            biggest[i] = biggest[j] + difference;
+            smallest[j] = 1.max(1 - difference);
+            smallest[i] = smallest[j] + difference;
+        }
+    }
+
+    let mut input = String::new();
+    for (div, check, offset) in blocks {
+        let _ = write!(
+            input,
+            "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\neql x w\neql x 0\n\
+             mul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {}\n\
+             mul y x\nadd z y\n",
+            div, check, offset
+        );
+    }
+
+    let number = |digits: [i64; 14]| comma_separated(digits).replace(',', "");
+
+    Generated {
+        input,
+        answers: vec![
+            (Part::One, Answer::Text(number(biggest))),
+            (Part::Two, Answer::Text(number(smallest))),
+        ],
+    }
+}
+
+/// A crowded sea floor, so the herds get stuck at some point
+fn day25(rng: &mut Rng) -> String {
+    let rows = (0..137).map(|_| {
+        (0..139)
+            .map(|_| match rng.below(20) {
+                0..=6 => '>',
+                7..=13 => 'v',
+                _ => '.',
+            })
+            .collect::<String>()
+    });
+
+    lines(rows.collect::<Vec<_>>())
+}
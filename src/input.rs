@@ -0,0 +1,98 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+const BUNDLED: [&str; 25] = [
+    include_str!("../problems/problem1"),
+    include_str!("../problems/problem2"),
+    include_str!("../problems/problem3"),
+    include_str!("../problems/problem4"),
+    include_str!("../problems/problem5"),
+    include_str!("../problems/problem6"),
+    include_str!("../problems/problem7"),
+    include_str!("../problems/problem8"),
+    include_str!("../problems/problem9"),
+    include_str!("../problems/problem10"),
+    include_str!("../problems/problem11"),
+    include_str!("../problems/problem12"),
+    include_str!("../problems/problem13"),
+    include_str!("../problems/problem14"),
+    include_str!("../problems/problem15"),
+    include_str!("../problems/problem16"),
+    include_str!("../problems/problem17"),
+    include_str!("../problems/problem18"),
+    include_str!("../problems/problem19"),
+    include_str!("../problems/problem20"),
+    include_str!("../problems/problem21"),
+    include_str!("../problems/problem22"),
+    include_str!("../problems/problem23"),
+    include_str!("../problems/problem24"),
+    include_str!("../problems/problem25"),
+];
+
+const BUNDLED_ANSWERS: &str = include_str!("../problems/answers");
+
+/// Where the puzzle inputs are read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The inputs in problems/, compiled into the binary
+    Bundled,
+    /// A single file, only usable for one day
+    File(PathBuf),
+    /// A directory laid out like problems/, i.e. containing problem1, ..., problem25
+    Directory(PathBuf),
+    /// Standard input, only usable for one day
+    Stdin,
+}
+
+impl InputSource {
+    /// Interpret a command line argument: "-" is stdin, directories are searched for
+    /// problemN files and everything else is read as is
+    pub fn from_arg(arg: &str) -> Self {
+        let path = Path::new(arg);
+
+        if arg == "-" {
+            InputSource::Stdin
+        } else if path.is_dir() {
+            InputSource::Directory(path.to_owned())
+        } else {
+            InputSource::File(path.to_owned())
+        }
+    }
+
+    /// Whether this source can provide the input of more than one day
+    pub fn serves_all_days(&self) -> bool {
+        matches!(self, InputSource::Bundled | InputSource::Directory(_))
+    }
+
+    pub fn load(&self, day: u8) -> crate::Result<String> {
+        match self {
+            InputSource::Bundled => {
+                let idx = (day as usize).checked_sub(1).ok_or(crate::Error::NoInput)?;
+                let input = BUNDLED.get(idx).ok_or(crate::Error::NoInput)?;
+
+                Ok(input.to_string())
+            }
+            InputSource::File(path) => Ok(std::fs::read_to_string(path)?),
+            InputSource::Directory(dir) => {
+                Ok(std::fs::read_to_string(dir.join(format!("problem{}", day)))?)
+            }
+            InputSource::Stdin => {
+                let mut input = String::new();
+                std::io::stdin().read_to_string(&mut input)?;
+
+                Ok(input)
+            }
+        }
+    }
+
+    /// Load the expected answers that belong to the inputs, if there are any
+    pub fn load_answers(&self) -> crate::Result<Option<String>> {
+        match self {
+            InputSource::Bundled => Ok(Some(BUNDLED_ANSWERS.to_string())),
+            InputSource::Directory(dir) => Ok(Some(std::fs::read_to_string(dir.join("answers"))?)),
+            InputSource::File(_) | InputSource::Stdin => Ok(None),
+        }
+    }
+}
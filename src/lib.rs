@@ -0,0 +1,46 @@
+//! Solutions to Advent of Code 2021. Every day has its own module with the input parser, the
+//! domain types and a type implementing [`Solution`], which solves both parts. [`solvers`]
+//! lists all days, the other modules contain what the runner needs on top of that.
+
+pub mod alloc;
+pub mod bench;
+pub mod error;
+pub mod generate;
+pub mod input;
+pub mod parity;
+pub mod pool;
+pub mod property;
+pub mod report;
+pub mod solution;
+pub mod solvers;
+pub mod util;
+pub mod verify;
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+pub use error::{Error, Result};
+pub use solution::{Answer, Solution};
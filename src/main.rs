@@ -1,108 +1,589 @@
-#![allow(dead_code)]
-use std::{
-    io::{self},
-    num::ParseIntError,
-};
-
-mod util;
-
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-fn main() -> crate::Result<()> {
-    let solvers = [
-        day1::solve,
-        day2::solve,
-        day3::solve,
-        day4::solve,
-        day5::solve,
-        day6::solve,
-        day7::solve,
-        day8::solve,
-        day9::solve,
-        day10::solve,
-        day11::solve,
-        day12::solve,
-        day13::solve,
-        day14::solve,
-        day15::solve,
-        day16::solve,
-        day17::solve,
-        day18::solve,
-        day19::solve,
-        day20::solve,
-        day21::solve,
-        day22::solve,
-        day23::solve,
-        day24::solve,
-        day25::solve,
-    ];
-
-    for (i, solver) in solvers.into_iter().enumerate() {
-        let day = i + 1;
-        println!("Day {}", day);
-
-        let now = std::time::Instant::now();
-        let _ = solver()?;
-        let took = now.elapsed();
-
-        println!("Took {} ms ({} ns)", took.as_millis(), took.as_nanos());
-        println!()
+use aoc2021::{alloc, bench, generate, parity, pool, report, solvers, solvers::Part, verify, Answer};
+
+mod cli;
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: alloc::Counting = alloc::Counting;
+
+fn main() -> aoc2021::Result<()> {
+    let args = match cli::Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(msg) => {
+            eprintln!("error: {}\n\n{}", msg, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    match args.command {
+        cli::Command::Help => println!("{}", cli::USAGE),
+        cli::Command::List => {
+            for solver in &solvers::SOLVERS {
+                let variants: Vec<_> = solver.variant_names().collect();
+                println!(
+                    "Day {:>2}: {} ({})",
+                    solver.day,
+                    solver.title,
+                    variants.join(", ")
+                );
+            }
+        }
+        cli::Command::Run => {
+            if !run(&args)? {
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Verify => {
+            if !verify(&args)? {
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Bench => bench(&args)?,
+        cli::Command::Parity => {
+            if !parity(&args)? {
+                std::process::exit(1);
+            }
+        }
+        cli::Command::Generate => generate(&args)?,
     }
 
     Ok(())
 }
 
-#[non_exhaustive]
-#[derive(Debug)]
-pub enum Error {
-    IOError(io::Error),
-    ParseIntError(ParseIntError),
-    NoInput,
-    InvalidInput,
+/// The solver of a day, or of the selected variant of it
+fn select_solver(args: &cli::Args, day: u8) -> aoc2021::Result<solvers::Solver> {
+    let solver = solvers::get(day).ok_or(aoc2021::Error::InvalidInput)?;
+
+    solver.variant(
+        args.variant
+            .as_deref()
+            .unwrap_or(solvers::Solver::DEFAULT_VARIANT),
+    )
+}
+
+/// Solve the selected days and print the results in the selected format. A day that fails
+/// does not stop the others, returns whether all days succeeded. With more than one job the
+/// days are solved concurrently, but still printed in order.
+fn run(args: &cli::Args) -> aoc2021::Result<bool> {
+    let start = std::time::Instant::now();
+
+    let solve_day = |day: &u8| {
+        let mut explanations = Vec::new();
+
+        let result = select_solver(args, *day).and_then(|solver| {
+                let input = args.input.load(*day)?;
+                let result = solver.solve_timed(&input, args.parts)?;
+
+                if args.explain {
+                    for part in [Part::One, Part::Two] {
+                        if args.parts.contains(part) {
+                            explanations.push((part, solver.explain(&input, part)));
+                        }
+                    }
+                }
+
+                Ok(result)
+            });
+
+        let report = report::DayReport {
+            day: *day,
+            parts: args.parts,
+            result,
+        };
+
+        (report, explanations)
+    };
+
+    let print = |(report, explanations): &(report::DayReport, Explanations)| {
+        if args.format == cli::Format::Text {
+            print_report(report);
+            print_explanations(explanations);
+        }
+    };
+
+    let results = if args.jobs > 1 {
+        let results = pool::map_ordered(&args.days, args.jobs, solve_day);
+        results.iter().for_each(print);
+
+        results
+    } else {
+        let mut results = Vec::new();
+
+        for day in &args.days {
+            let result = solve_day(day);
+            print(&result);
+
+            results.push(result);
+        }
+
+        results
+    };
+
+    let reports: Vec<_> = results.into_iter().map(|(report, _)| report).collect();
+
+    let wall_clock = start.elapsed();
+
+    match args.format {
+        cli::Format::Text => {
+            print_breakdown(&reports);
+            println!(
+                "Wall clock: {} ({} job{})",
+                bench::format_duration(wall_clock),
+                args.jobs,
+                if args.jobs == 1 { "" } else { "s" }
+            );
+            print_allocations(&reports);
+        }
+        cli::Format::Json => println!("{}", report::to_json(&reports)),
+        cli::Format::Csv => print!("{}", report::to_csv(&reports)),
+    }
+
+    Ok(reports.iter().all(|r| r.result.is_ok()))
+}
+
+/// How the answers of the parts of a day came about, see `Solver::explain`
+type Explanations = Vec<(Part, aoc2021::Result<Vec<String>>)>;
+
+fn print_explanations(explanations: &Explanations) {
+    for (part, explanation) in explanations {
+        match explanation {
+            Ok(lines) if lines.is_empty() => {}
+            Ok(lines) => {
+                println!("Explanation of problem {}:", part.number());
+                lines.iter().for_each(|line| println!("{}", line));
+            }
+            Err(e) => println!("Explanation of problem {} failed: {}", part.number(), e),
+        }
+    }
+}
+
+fn print_report(report: &report::DayReport) {
+    println!("Day {}", report.day);
+
+    match &report.result {
+        Ok((answers, timings)) => {
+            for (i, answer) in answers.iter().enumerate() {
+                match answer {
+                    Some(Answer::None) | None => {}
+                    Some(answer) if answer.is_multiline() => {
+                        println!("Problem {}:\n{}", i + 1, answer)
+                    }
+                    Some(answer) => println!("Problem {}: {}", i + 1, answer),
+                }
+            }
+
+            let took = timings.total;
+            println!("Took {} ms ({} ns)", took.as_millis(), took.as_nanos());
+        }
+        Err(e) => println!("Error: {}", e),
+    }
+
+    println!();
 }
 
-type Result<T> = std::result::Result<T, Error>;
+/// Print a table with the time every phase of every day took
+fn print_breakdown(reports: &[report::DayReport]) {
+    let breakdown = reports
+        .iter()
+        .filter_map(|r| Some((r.day, r.result.as_ref().ok()?.1)))
+        .collect::<Vec<_>>();
+
+    if breakdown.is_empty() {
+        return;
+    }
+
+    let format = |d: Option<std::time::Duration>| d.map(bench::format_duration).unwrap_or_default();
+
+    println!(
+        "Day  {:>10}  {:>10}  {:>10}  {:>10}",
+        "parse", "part 1", "part 2", "total"
+    );
+
+    let mut sum = solvers::Timings::default();
+
+    for (day, timings) in &breakdown {
+        println!(
+            "{:>3}  {:>10}  {:>10}  {:>10}  {:>10}",
+            day,
+            format(Some(timings.parse)),
+            format(timings.part1),
+            format(timings.part2),
+            format(Some(timings.total)),
+        );
+
+        sum.parse += timings.parse;
+        sum.part1 = timings.part1.map(|t| t + sum.part1.unwrap_or_default());
+        sum.part2 = timings.part2.map(|t| t + sum.part2.unwrap_or_default());
+        sum.total += timings.total;
+    }
 
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{:?}", self))
+    if breakdown.len() > 1 {
+        println!(
+            "all  {:>10}  {:>10}  {:>10}  {:>10}",
+            format(Some(sum.parse)),
+            format(sum.part1),
+            format(sum.part2),
+            format(Some(sum.total)),
+        );
     }
 }
 
-impl std::error::Error for Error {}
+/// Print what every phase of every day allocated, if allocations were counted
+fn print_allocations(reports: &[report::DayReport]) {
+    let timings = reports
+        .iter()
+        .filter_map(|r| Some((r.day, r.result.as_ref().ok()?.1)))
+        .filter(|(_, timings)| timings.allocations(solvers::Phase::Total).is_some())
+        .collect::<Vec<_>>();
 
-impl From<io::Error> for Error {
-    fn from(val: io::Error) -> Self {
-        Error::IOError(val)
+    if timings.is_empty() {
+        return;
+    }
+
+    println!();
+    println!(
+        "Day  Phase  {:>11}  {:>11}  {:>11}",
+        "allocations", "bytes", "peak"
+    );
+
+    for (day, timings) in &timings {
+        for phase in solvers::Phase::ALL {
+            if let Some(allocations) = timings.allocations(phase) {
+                println!(
+                    "{:>3}  {:<5}  {:>11}  {:>11}  {:>11}",
+                    day,
+                    phase.name(),
+                    allocations.count,
+                    alloc::format_bytes(allocations.bytes),
+                    alloc::format_bytes(allocations.peak),
+                );
+            }
+        }
     }
 }
 
-impl From<ParseIntError> for Error {
-    fn from(inner: ParseIntError) -> Self {
-        Error::ParseIntError(inner)
+/// Solve the selected days and compare the answers to the expected ones. Returns whether
+/// all answers were correct.
+fn verify(args: &cli::Args) -> aoc2021::Result<bool> {
+    let answers = match &args.answers {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => args.input.load_answers()?.ok_or(aoc2021::Error::NoInput)?,
+    };
+    let expected = verify::ExpectedAnswers::parse(&answers)?;
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for &day in &args.days {
+        // A missing input or variant fails the day, like a solver error does
+        let answers = select_solver(args, day).and_then(|solver| {
+            let input = args.input.load(day)?;
+            solver.solve(&input, args.parts)
+        });
+
+        let answers = match answers {
+            Ok(answers) => answers,
+            Err(e) => {
+                println!("Day {:>2}: ERROR ({})", day, e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        for (part, answer) in [Part::One, Part::Two].into_iter().zip(answers) {
+            let answer = match answer {
+                Some(Answer::None) | None => continue,
+                Some(answer) => answer,
+            };
+
+            let nr = part.number();
+
+            match expected.check(day, part, &answer) {
+                verify::Outcome::Pass => {
+                    println!("Day {:>2} / Part {}: ok", day, nr);
+                    passed += 1;
+                }
+                verify::Outcome::Fail { expected } => {
+                    println!(
+                        "Day {:>2} / Part {}: FAILED (expected {}, got {})",
+                        day, nr, expected, answer
+                    );
+                    failed += 1;
+                }
+                verify::Outcome::Missing => {
+                    println!("Day {:>2} / Part {}: missing (got {})", day, nr, answer);
+                    missing += 1;
+                }
+            }
+        }
     }
+
+    println!();
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+
+    Ok(failed == 0)
+}
+
+/// Solve the selected days and run the Python reference solutions on the same inputs, then
+/// compare the answers and how long both took. Returns whether all answers agreed.
+fn parity(args: &cli::Args) -> aoc2021::Result<bool> {
+    let (mut agreed, mut diverged) = (0, 0);
+    let mut skipped = Vec::new();
+
+    for &day in &args.days {
+        let script = match parity::script(&args.python, day) {
+            Some(script) => script,
+            None => {
+                skipped.push(day.to_string());
+                continue;
+            }
+        };
+
+        // A missing input or variant counts as diverged, like a solver error does
+        let loaded = select_solver(args, day).and_then(|solver| {
+            let input = args.input.load(day)?;
+            Ok((solver, input))
+        });
+
+        let (solver, input) = match loaded {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                println!("Day {:>2}: ERROR ({})", day, e);
+                diverged += 1;
+                continue;
+            }
+        };
+
+        // The script reads the input from a file, wherever it came from
+        let path = std::env::temp_dir().join(format!(
+            "aoc2021-parity-{}-problem{}",
+            std::process::id(),
+            day
+        ));
+        std::fs::write(&path, &input)?;
+        let python = parity::run(&script, &path);
+        std::fs::remove_file(&path)?;
+
+        let (rust, python) = match (solver.solve_timed(&input, args.parts), python) {
+            (Ok(rust), Ok(python)) => (rust, python),
+            (Err(e), _) | (_, Err(e)) => {
+                println!("Day {:>2}: ERROR ({})", day, e);
+                diverged += 1;
+                continue;
+            }
+        };
+
+        let (answers, timings) = rust;
+
+        for (i, (answer, expected)) in answers.iter().zip(&python.answers).enumerate() {
+            let answer = match answer {
+                Some(answer) => answer.to_string(),
+                None => continue,
+            };
+
+            match expected {
+                Some(expected) if *expected == answer => {
+                    println!("Day {:>2} / Part {}: ok ({})", day, i + 1, answer);
+                    agreed += 1;
+                }
+                Some(expected) => {
+                    println!(
+                        "Day {:>2} / Part {}: DIVERGED (rust {}, python {})",
+                        day,
+                        i + 1,
+                        answer,
+                        expected
+                    );
+                    diverged += 1;
+                }
+                None => {
+                    println!(
+                        "Day {:>2} / Part {}: DIVERGED (rust {}, python printed nothing)",
+                        day,
+                        i + 1,
+                        answer
+                    );
+                    diverged += 1;
+                }
+            }
+        }
+
+        // Python always solves both parts, so this is only a fair comparison without -p
+        println!(
+            "Day {:>2}: rust {}, python {} ({:.1}x as long)",
+            day,
+            bench::format_duration(timings.total),
+            bench::format_duration(python.took),
+            python.took.as_secs_f64() / timings.total.as_secs_f64().max(1e-9)
+        );
+    }
+
+    println!();
+    if !skipped.is_empty() {
+        println!("Skipped without a reference solution: {}", skipped.join(", "));
+    }
+    println!("{} agreed, {} diverged", agreed, diverged);
+
+    Ok(diverged == 0)
+}
+
+/// Benchmark the selected days, print statistics for every phase and compare them to the
+/// baseline if one was given
+fn bench(args: &cli::Args) -> aoc2021::Result<()> {
+    if args.variants {
+        return bench_variants(args);
+    }
+
+    let baseline = match &args.baseline {
+        Some(path) => Some(bench::Baseline::load(path)?),
+        None => None,
+    };
+
+    println!(
+        "Day  Phase  {:>10}  {:>10}  {:>10}  {:>10}",
+        "min", "median", "mean", "stddev"
+    );
+
+    let mut results = Vec::new();
+    let mut regressions = 0;
+
+    for &day in &args.days {
+        let solver = select_solver(args, day)?;
+        let input = args.input.load(day)?;
+
+        let result = bench::bench_day(&solver, &input, args.parts, args.warmup, args.iterations)?;
+
+        for (phase, stats) in &result.phases {
+            let mut line = format!(
+                "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>10}",
+                day,
+                phase.name(),
+                bench::format_duration(stats.min),
+                bench::format_duration(stats.median),
+                bench::format_duration(stats.mean),
+                bench::format_duration(stats.stddev),
+            );
+
+            if let Some(old) = baseline.as_ref().and_then(|b| b.get(day, *phase)) {
+                let change = bench::relative_change(stats, old) * 100.0;
+                line += &format!("  {:>+7.1}%", change);
+
+                if change > args.threshold {
+                    line += "  REGRESSION";
+                    regressions += 1;
+                }
+            }
+
+            println!("{}", line);
+        }
+
+        results.push(result);
+    }
+
+    if baseline.is_some() {
+        println!();
+        println!(
+            "{} regression(s) above {}% compared to the baseline",
+            regressions, args.threshold
+        );
+    }
+
+    if let Some(path) = &args.save {
+        bench::Baseline::save(path, &results)?;
+    }
+
+    Ok(())
+}
+
+/// Benchmark every variant of the selected days and print them in one table, with the change
+/// of the median of every phase compared to the default solution
+fn bench_variants(args: &cli::Args) -> aoc2021::Result<()> {
+    let width = args
+        .days
+        .iter()
+        .filter_map(|&day| solvers::get(day))
+        .flat_map(|solver| solver.variant_names())
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+
+    println!(
+        "Day  {:<width$}  Phase  {:>10}  {:>10}  {:>10}  {:>10}  {:>8}",
+        "Variant",
+        "min",
+        "median",
+        "mean",
+        "stddev",
+        "change",
+        width = width
+    );
+
+    for &day in &args.days {
+        let solver = solvers::get(day).ok_or(aoc2021::Error::InvalidInput)?;
+        let input = args.input.load(day)?;
+
+        let mut default = None;
+
+        for name in solver.variant_names() {
+            let variant = solver.variant(name)?;
+            let result =
+                bench::bench_day(&variant, &input, args.parts, args.warmup, args.iterations)?;
+
+            for (phase, stats) in &result.phases {
+                let change = default
+                    .as_ref()
+                    .and_then(|d: &bench::DayBench| d.phases.iter().find(|(p, _)| p == phase))
+                    .map(|(_, old)| format!("{:>+7.1}%", bench::relative_change(stats, old) * 100.0))
+                    .unwrap_or_default();
+
+                println!(
+                    "{:>3}  {:<width$}  {:<5}  {:>10}  {:>10}  {:>10}  {:>10}  {:>8}",
+                    day,
+                    name,
+                    phase.name(),
+                    bench::format_duration(stats.min),
+                    bench::format_duration(stats.median),
+                    bench::format_duration(stats.mean),
+                    bench::format_duration(stats.stddev),
+                    change,
+                    width = width
+                );
+            }
+
+            default.get_or_insert(result);
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a random input for each selected day to the output directory, together with an
+/// answers file of the answers that are known from generating them, so that verify can
+/// check them
+fn generate(args: &cli::Args) -> aoc2021::Result<()> {
+    std::fs::create_dir_all(&args.output)?;
+
+    let mut answers = verify::ExpectedAnswers::default();
+
+    for &day in &args.days {
+        let generated = generate::generate(day, args.seed).ok_or(aoc2021::Error::InvalidInput)?;
+
+        let path = args.output.join(format!("problem{}", day));
+        std::fs::write(&path, &generated.input)?;
+
+        for (part, answer) in &generated.answers {
+            answers.insert(day, *part, answer);
+        }
+
+        println!(
+            "Day {:>2}: {} ({} known answer{})",
+            day,
+            path.display(),
+            generated.answers.len(),
+            if generated.answers.len() == 1 { "" } else { "s" }
+        );
+    }
+
+    std::fs::write(args.output.join("answers"), answers.to_string())?;
+
+    Ok(())
 }
@@ -0,0 +1,62 @@
+//! Running the Python reference solutions in python/, to compare them with the Rust ones. A
+//! script is called with the path of the input as its only argument and prints its answers
+//! as `Problem 1: <answer>` and `Problem 2: <answer>`.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant},
+};
+
+/// The interpreter the scripts are run with
+pub const PYTHON: &str = "python3";
+
+/// The answers a script printed and how long it ran, including the start of the interpreter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonRun {
+    pub answers: [Option<String>; 2],
+    pub took: Duration,
+}
+
+/// The reference script of a day in the given directory, None if there is none
+pub fn script(dir: &Path, day: u8) -> Option<PathBuf> {
+    let path = dir.join(format!("day{}.py", day));
+
+    path.is_file().then_some(path)
+}
+
+/// Run a script on the input file. Fails if the interpreter cannot be started or the script
+/// exits with an error.
+pub fn run(script: &Path, input: &Path) -> crate::Result<PythonRun> {
+    let start = Instant::now();
+    let output = Command::new(PYTHON).arg(script).arg(input).output()?;
+    let took = start.elapsed();
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().last().unwrap_or("no output").to_owned();
+
+        return Err(io::Error::other(format!("{} failed: {}", script.display(), reason)).into());
+    }
+
+    Ok(PythonRun {
+        answers: parse_answers(&String::from_utf8_lossy(&output.stdout)),
+        took,
+    })
+}
+
+/// The answers in the output of a script, other lines are ignored
+pub fn parse_answers(output: &str) -> [Option<String>; 2] {
+    let mut answers = [None, None];
+
+    for line in output.lines() {
+        if let Some(answer) = line.strip_prefix("Problem 1:") {
+            answers[0] = Some(answer.trim().to_owned());
+        } else if let Some(answer) = line.strip_prefix("Problem 2:") {
+            answers[1] = Some(answer.trim().to_owned());
+        }
+    }
+
+    answers
+}
@@ -0,0 +1,191 @@
+//! A minimal property testing layer. A property is checked against random values from a
+//! generator, and a value it fails for is shrunk to a minimal one before it is reported.
+//! Shrinking is greedy: of the simpler candidates of a failing value, the first that still
+//! fails replaces it, until none does.
+//!
+//! ```
+//! use aoc2021::property::{self, shrink_int, shrink_vec};
+//!
+//! // Not every list of numbers below 1000 sums up to less than 100
+//! let failure = property::check(
+//!     100,
+//!     1,
+//!     |rng| (0..rng.below(10)).map(|_| rng.below(1000)).collect::<Vec<_>>(),
+//!     |v| shrink_vec(v, |&x| shrink_int(x, 0)),
+//!     |v| property::ensure(v.iter().sum::<u64>() < 100, "sum too big"),
+//! )
+//! .unwrap_err();
+//!
+//! assert_eq!(failure.value, vec![100]);
+//! ```
+
+use std::fmt::{Debug, Display};
+
+use crate::{generate::Rng, util::Integer};
+
+/// How many shrinking steps are tried at most before the value is reported as it is
+const MAX_SHRINK_STEPS: usize = 10_000;
+
+/// A value a property does not hold for, after shrinking
+#[derive(Debug, Clone)]
+pub struct Counterexample<T> {
+    pub value: T,
+    pub message: String,
+    /// The value before shrinking
+    pub original: T,
+    pub shrink_steps: usize,
+    /// The seed of the generator that produced the original value
+    pub seed: u64,
+}
+
+impl<T: Debug> Display for Counterexample<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "property failed for {:?}: {}\n(shrunk in {} steps from {:?}, generated with seed {})",
+            self.value, self.message, self.shrink_steps, self.original, self.seed
+        )
+    }
+}
+
+/// Ok if the condition holds, the message otherwise
+pub fn ensure(condition: bool, message: impl Display) -> Result<(), String> {
+    if condition {
+        Ok(())
+    } else {
+        Err(message.to_string())
+    }
+}
+
+/// Ok if both values are equal, a message showing both otherwise
+pub fn ensure_eq<T: PartialEq + Debug>(left: T, right: T) -> Result<(), String> {
+    ensure(left == right, format!("{:?} != {:?}", left, right))
+}
+
+/// Check the property for `cases` values from `generate`, with every case getting its own
+/// seed derived from `seed`. Returns the shrunk counterexample of the first failing case.
+pub fn check<T, G, S, P>(
+    cases: usize,
+    seed: u64,
+    mut generate: G,
+    shrink: S,
+    property: P,
+) -> Result<(), Counterexample<T>>
+where
+    T: Clone,
+    G: FnMut(&mut Rng) -> T,
+    S: Fn(&T) -> Vec<T>,
+    P: Fn(&T) -> Result<(), String>,
+{
+    let mut seeds = Rng::new(seed);
+
+    for _ in 0..cases {
+        let seed = seeds.next_u64();
+        let original = generate(&mut Rng::new(seed));
+
+        let message = match property(&original) {
+            Ok(()) => continue,
+            Err(message) => message,
+        };
+
+        let mut value = original.clone();
+        let mut message = message;
+        let mut shrink_steps = 0;
+
+        'shrink: while shrink_steps < MAX_SHRINK_STEPS {
+            for candidate in shrink(&value) {
+                if let Err(m) = property(&candidate) {
+                    value = candidate;
+                    message = m;
+                    shrink_steps += 1;
+
+                    continue 'shrink;
+                }
+            }
+
+            break;
+        }
+
+        return Err(Counterexample {
+            value,
+            message,
+            original,
+            shrink_steps,
+            seed,
+        });
+    }
+
+    Ok(())
+}
+
+/// Like [`check`], but panics with the counterexample, for use in tests
+pub fn assert<T, G, S, P>(cases: usize, seed: u64, generate: G, shrink: S, property: P)
+where
+    T: Clone + Debug,
+    G: FnMut(&mut Rng) -> T,
+    S: Fn(&T) -> Vec<T>,
+    P: Fn(&T) -> Result<(), String>,
+{
+    if let Err(counterexample) = check(cases, seed, generate, shrink, property) {
+        panic!("{}", counterexample);
+    }
+}
+
+/// Values between `x` and `target`, closest to the target first
+pub fn shrink_int<T: Integer>(x: T, target: T) -> Vec<T> {
+    let two = T::ONE + T::ONE;
+    let mut candidates = Vec::new();
+
+    // Halve the distance to x over and over, which gets down to 1 after log steps. The
+    // distance is always computed as the bigger minus the smaller value, so it does not
+    // overflow for unsigned types.
+    let mut distance = if x > target { x - target } else { target - x };
+
+    while distance > T::ZERO {
+        candidates.push(if x > target {
+            x - distance
+        } else {
+            x + distance
+        });
+        distance = distance / two;
+    }
+
+    candidates
+}
+
+/// Shorter vectors, with chunks removed, followed by vectors with a single element shrunk
+pub fn shrink_vec<T: Clone, F: Fn(&T) -> Vec<T>>(v: &[T], shrink_item: F) -> Vec<Vec<T>> {
+    let mut candidates = Vec::new();
+
+    let mut chunk = v.len();
+    while chunk > 0 {
+        for start in (0..=v.len() - chunk).step_by(chunk) {
+            candidates.push([&v[..start], &v[start + chunk..]].concat());
+        }
+
+        chunk /= 2;
+    }
+
+    for (i, item) in v.iter().enumerate() {
+        for shrunk in shrink_item(item) {
+            let mut candidate = v.to_vec();
+            candidate[i] = shrunk;
+
+            candidates.push(candidate);
+        }
+    }
+
+    candidates
+}
+
+/// Pairs with either component shrunk
+pub fn shrink_pair<A: Clone, B: Clone>(
+    (a, b): &(A, B),
+    shrink_a: impl Fn(&A) -> Vec<A>,
+    shrink_b: impl Fn(&B) -> Vec<B>,
+) -> Vec<(A, B)> {
+    let first = shrink_a(a).into_iter().map(|a| (a, b.clone()));
+    let second = shrink_b(b).into_iter().map(|b| (a.clone(), b));
+
+    first.chain(second).collect()
+}
@@ -0,0 +1,156 @@
+use std::fmt::Write;
+
+use crate::{
+    solution::Answer,
+    solvers::{Part, Parts, Timings},
+};
+
+/// The outcome of running one day, either the answers with their timings or the error
+/// that stopped the day
+pub struct DayReport {
+    pub day: u8,
+    pub parts: Parts,
+    pub result: crate::Result<([Option<Answer>; 2], Timings)>,
+}
+
+impl DayReport {
+    fn selected_parts(&self) -> impl Iterator<Item = Part> + '_ {
+        [Part::One, Part::Two]
+            .into_iter()
+            .filter(|p| self.parts.contains(*p))
+    }
+}
+
+/// Render the reports as a JSON array with one object per day
+pub fn to_json(reports: &[DayReport]) -> String {
+    let mut out = String::from("[");
+
+    for (i, report) in reports.iter().enumerate() {
+        let _ = write!(
+            out,
+            "{}\n  {{\"day\": {}, ",
+            if i > 0 { "," } else { "" },
+            report.day
+        );
+
+        match &report.result {
+            Ok((answers, timings)) => {
+                let _ = write!(
+                    out,
+                    "\"status\": \"ok\", \"parse_ns\": {}, \"total_ns\": {}, \"parts\": [",
+                    timings.parse.as_nanos(),
+                    timings.total.as_nanos()
+                );
+
+                for (j, part) in report.selected_parts().enumerate() {
+                    let idx = part.number() as usize - 1;
+
+                    let answer = match &answers[idx] {
+                        Some(Answer::None) | None => "null".to_owned(),
+                        Some(answer) => json_string(&answer.to_string()),
+                    };
+                    let took = match part {
+                        Part::One => timings.part1,
+                        Part::Two => timings.part2,
+                    };
+
+                    let _ = write!(
+                        out,
+                        "{}{{\"part\": {}, \"answer\": {}, \"ns\": {}}}",
+                        if j > 0 { ", " } else { "" },
+                        part.number(),
+                        answer,
+                        took.unwrap_or_default().as_nanos()
+                    );
+                }
+
+                out.push_str("]}");
+            }
+            Err(e) => {
+                let _ = write!(
+                    out,
+                    "\"status\": \"error\", \"error\": {}}}",
+                    json_string(&e.to_string())
+                );
+            }
+        }
+    }
+
+    out.push_str("\n]");
+    out
+}
+
+/// Render the reports as CSV with one row per day and part
+pub fn to_csv(reports: &[DayReport]) -> String {
+    let mut out = String::from("day,part,status,answer,parse_ns,part_ns,total_ns,error\n");
+
+    for report in reports {
+        for part in report.selected_parts() {
+            let _ = match &report.result {
+                Ok((answers, timings)) => {
+                    let idx = part.number() as usize - 1;
+
+                    let answer = match &answers[idx] {
+                        Some(answer) => answer.to_string(),
+                        None => String::new(),
+                    };
+                    let took = match part {
+                        Part::One => timings.part1,
+                        Part::Two => timings.part2,
+                    };
+
+                    writeln!(
+                        out,
+                        "{},{},ok,{},{},{},{},",
+                        report.day,
+                        part.number(),
+                        csv_field(&answer),
+                        timings.parse.as_nanos(),
+                        took.unwrap_or_default().as_nanos(),
+                        timings.total.as_nanos()
+                    )
+                }
+                Err(e) => writeln!(
+                    out,
+                    "{},{},error,,,,,{}",
+                    report.day,
+                    part.number(),
+                    csv_field(&e.to_string())
+                ),
+            };
+        }
+    }
+
+    out
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+/// Quote a field if it contains anything that would break the row
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
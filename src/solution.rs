@@ -0,0 +1,105 @@
+use std::{any::Any, fmt::Display};
+
+use crate::solvers::Part;
+
+/// The answer to one part of a puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+    /// A multi-line answer, e.g. letters drawn as ASCII art. Every entry is one row.
+    Grid(Vec<String>),
+    /// The part has no answer (only the case for the second part of day 25)
+    None,
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Grid(_))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(x) => write!(f, "{}", x),
+            Answer::Signed(x) => write!(f, "{}", x),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+            Answer::None => Ok(()),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($variant:ident, $target:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(x: $t) -> Self {
+                    Answer::$variant(x as $target)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(Unsigned, u64, u8, u16, u32, u64, usize);
+impl_from_int!(Signed, i64, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+/// A solution to one day: the input is parsed once and then passed to both parts
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> crate::Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> crate::Result<Answer>;
+
+    fn part2(input: &Self::Input) -> crate::Result<Answer>;
+
+    /// Lines that show how the answer to a part came about, e.g. the path whose cost it is.
+    /// Most days have nothing to show.
+    fn explain(_input: &Self::Input, _part: Part) -> crate::Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Type erased version of `Solution`, so that the solutions of all days can be stored
+/// side by side. It is implemented for every `Solution`.
+pub trait DynSolution: Sync {
+    fn parse(&self, input: &str) -> crate::Result<Box<dyn Any>>;
+
+    fn part1(&self, input: &dyn Any) -> crate::Result<Answer>;
+
+    fn part2(&self, input: &dyn Any) -> crate::Result<Answer>;
+
+    fn explain(&self, input: &dyn Any, part: Part) -> crate::Result<Vec<String>>;
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync,
+    S::Input: 'static,
+{
+    fn parse(&self, input: &str) -> crate::Result<Box<dyn Any>> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn part1(&self, input: &dyn Any) -> crate::Result<Answer> {
+        S::part1(input.downcast_ref().ok_or(crate::Error::InvalidInput)?)
+    }
+
+    fn part2(&self, input: &dyn Any) -> crate::Result<Answer> {
+        S::part2(input.downcast_ref().ok_or(crate::Error::InvalidInput)?)
+    }
+
+    fn explain(&self, input: &dyn Any, part: Part) -> crate::Result<Vec<String>> {
+        S::explain(input.downcast_ref().ok_or(crate::Error::InvalidInput)?, part)
+    }
+}
@@ -0,0 +1,234 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    alloc::{self, Allocations},
+    day1, day10, day11, day12, day13, day14, day15, day16, day17, day18, day19, day2, day20,
+    day21, day22, day23, day24, day25, day3, day4, day5, day6, day7, day8, day9,
+    solution::{Answer, DynSolution},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// A separately timed step of solving a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+    /// All of the above together
+    Total,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 4] = [Phase::Parse, Phase::Part1, Phase::Part2, Phase::Total];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+            Phase::Total => "total",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Phase> {
+        Phase::ALL.iter().copied().find(|p| p.name() == name)
+    }
+}
+
+/// Which parts of a day should be solved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parts {
+    Both,
+    Only(Part),
+}
+
+impl Parts {
+    pub fn contains(self, part: Part) -> bool {
+        match self {
+            Parts::Both => true,
+            Parts::Only(p) => p == part,
+        }
+    }
+}
+
+/// How long each phase of solving a day took. Parts that were not run are `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+    pub total: Duration,
+    /// What each phase allocated, in the order of `Phase::ALL`. All `None` unless
+    /// allocations are counted, see [`crate::alloc`].
+    pub allocations: [Option<Allocations>; 4],
+}
+
+impl Timings {
+    pub fn get(&self, phase: Phase) -> Option<Duration> {
+        match phase {
+            Phase::Parse => Some(self.parse),
+            Phase::Part1 => self.part1,
+            Phase::Part2 => self.part2,
+            Phase::Total => Some(self.total),
+        }
+    }
+
+    pub fn allocations(&self, phase: Phase) -> Option<Allocations> {
+        let i = Phase::ALL.iter().position(|&p| p == phase)?;
+
+        self.allocations[i]
+    }
+}
+
+/// An alternative way of solving a day, e.g. with a different data structure. Variants give
+/// the same answers as the day's solution and can be selected by name instead of it.
+pub struct Variant {
+    pub name: &'static str,
+    pub solution: &'static dyn DynSolution,
+}
+
+pub struct Solver {
+    pub day: u8,
+    pub title: &'static str,
+    pub solution: &'static dyn DynSolution,
+    /// The alternatives to `solution`, see [`Variant`]
+    pub variants: &'static [Variant],
+}
+
+impl Solver {
+    /// The name under which `solution` itself can be selected
+    pub const DEFAULT_VARIANT: &'static str = "default";
+
+    /// The names of all variants, starting with the default one
+    pub fn variant_names(&self) -> impl Iterator<Item = &'static str> {
+        std::iter::once(Self::DEFAULT_VARIANT).chain(self.variants.iter().map(|v| v.name))
+    }
+
+    /// This day, but solved by the variant with the given name
+    pub fn variant(&self, name: &str) -> crate::Result<Solver> {
+        let solution = if name == Self::DEFAULT_VARIANT {
+            self.solution
+        } else {
+            self.variants
+                .iter()
+                .find(|v| v.name == name)
+                .ok_or_else(|| crate::Error::UnknownVariant(name.to_owned()))?
+                .solution
+        };
+
+        Ok(Solver {
+            day: self.day,
+            title: self.title,
+            solution,
+            variants: self.variants,
+        })
+    }
+
+    /// Parse the input and solve the selected parts. Parts that were not selected are `None`.
+    pub fn solve(&self, input: &str, parts: Parts) -> crate::Result<[Option<Answer>; 2]> {
+        Ok(self.solve_timed(input, parts)?.0)
+    }
+
+    /// Like `solve`, but also measures how long parsing and each of the parts took, and what
+    /// they allocated if allocations are counted
+    pub fn solve_timed(
+        &self,
+        input: &str,
+        parts: Parts,
+    ) -> crate::Result<([Option<Answer>; 2], Timings)> {
+        let start = Instant::now();
+        let mut answers = [None, None];
+        let mut timings = Timings::default();
+
+        let (result, total) = alloc::measure(|| -> crate::Result<()> {
+            let (input, allocations) = alloc::measure(|| self.solution.parse(input));
+            let input = input?;
+            timings.parse = start.elapsed();
+            timings.allocations[0] = allocations;
+
+            if parts.contains(Part::One) {
+                let now = Instant::now();
+                let (answer, allocations) = alloc::measure(|| self.solution.part1(input.as_ref()));
+                answers[0] = Some(answer?);
+                timings.part1 = Some(now.elapsed());
+                timings.allocations[1] = allocations;
+            }
+
+            if parts.contains(Part::Two) {
+                let now = Instant::now();
+                let (answer, allocations) = alloc::measure(|| self.solution.part2(input.as_ref()));
+                answers[1] = Some(answer?);
+                timings.part2 = Some(now.elapsed());
+                timings.allocations[2] = allocations;
+            }
+
+            Ok(())
+        });
+        // Parts can report parse errors too, for what they only find out while solving
+        result.map_err(|e| e.in_day(self.day))?;
+
+        timings.total = start.elapsed();
+        timings.allocations[3] = total;
+
+        Ok((answers, timings))
+    }
+
+    /// Parse the input and explain how the answer to the given part came about. Empty if the
+    /// day has nothing to show.
+    pub fn explain(&self, input: &str, part: Part) -> crate::Result<Vec<String>> {
+        let input = self
+            .solution
+            .parse(input)
+            .map_err(|e| e.in_day(self.day))?;
+
+        self.solution
+            .explain(input.as_ref(), part)
+            .map_err(|e| e.in_day(self.day))
+    }
+}
+
+pub const SOLVERS: [Solver; 25] = [
+    Solver { day: 1, title: "Sonar Sweep", solution: &day1::Day1, variants: day1::VARIANTS },
+    Solver { day: 2, title: "Dive!", solution: &day2::Day2, variants: &[] },
+    Solver { day: 3, title: "Binary Diagnostic", solution: &day3::Day3, variants: &[] },
+    Solver { day: 4, title: "Giant Squid", solution: &day4::Day4, variants: &[] },
+    Solver { day: 5, title: "Hydrothermal Venture", solution: &day5::Day5, variants: &[] },
+    Solver { day: 6, title: "Lanternfish", solution: &day6::Day6, variants: day6::VARIANTS },
+    Solver { day: 7, title: "The Treachery of Whales", solution: &day7::Day7, variants: &[] },
+    Solver { day: 8, title: "Seven Segment Search", solution: &day8::Day8, variants: &[] },
+    Solver { day: 9, title: "Smoke Basin", solution: &day9::Day9, variants: &[] },
+    Solver { day: 10, title: "Syntax Scoring", solution: &day10::Day10, variants: &[] },
+    Solver { day: 11, title: "Dumbo Octopus", solution: &day11::Day11, variants: &[] },
+    Solver { day: 12, title: "Passage Pathing", solution: &day12::Day12, variants: &[] },
+    Solver { day: 13, title: "Transparent Origami", solution: &day13::Day13, variants: &[] },
+    Solver { day: 14, title: "Extended Polymerization", solution: &day14::Day14, variants: &[] },
+    Solver { day: 15, title: "Chiton", solution: &day15::Day15, variants: &[] },
+    Solver { day: 16, title: "Packet Decoder", solution: &day16::Day16, variants: &[] },
+    Solver { day: 17, title: "Trick Shot", solution: &day17::Day17, variants: &[] },
+    Solver { day: 18, title: "Snailfish", solution: &day18::Day18, variants: &[] },
+    Solver { day: 19, title: "Beacon Scanner", solution: &day19::Day19, variants: &[] },
+    Solver { day: 20, title: "Trench Map", solution: &day20::Day20, variants: &[] },
+    Solver { day: 21, title: "Dirac Dice", solution: &day21::Day21, variants: &[] },
+    Solver { day: 22, title: "Reactor Reboot", solution: &day22::Day22, variants: &[] },
+    Solver { day: 23, title: "Amphipod", solution: &day23::Day23, variants: &[] },
+    Solver { day: 24, title: "Arithmetic Logic Unit", solution: &day24::Day24, variants: &[] },
+    Solver { day: 25, title: "Sea Cucumber", solution: &day25::Day25, variants: &[] },
+];
+
+pub fn get(day: u8) -> Option<&'static Solver> {
+    SOLVERS.iter().find(|s| s.day == day)
+}
@@ -1,10 +1,19 @@
 use std::{
     cmp::Ordering,
-    collections::BinaryHeap,
+    collections::{BinaryHeap, VecDeque},
     hash::{Hash, Hasher},
 };
 
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
+
+mod grid;
+mod memo;
+pub mod parse;
+mod vector;
+
+pub use grid::{Grid, GridGraph, Neighborhood};
+pub use memo::{Memo, Stats, Store, Table};
+pub use vector::{BoundingBox, Integer, Rotation, Vec2, Vec3, Vector};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Edge<V> {
@@ -12,16 +21,98 @@ pub struct Edge<V> {
     pub cost: u64
 }
 
+/// A path through a graph. `costs[i]` is the cost of the edge from `vertices[i]` to
+/// `vertices[i + 1]`, so there is one cost less than there are vertices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<V> {
+    pub vertices: Vec<V>,
+    pub costs: Vec<u64>,
+}
+
+impl<V> Path<V> {
+    /// The total cost of all edges
+    pub fn cost(&self) -> u64 {
+        self.costs.iter().sum()
+    }
+
+    /// The edges of the path as (from, to, cost)
+    pub fn edges(&self) -> impl Iterator<Item = (&V, &V, u64)> + '_ {
+        self.vertices
+            .windows(2)
+            .zip(&self.costs)
+            .map(|(pair, &cost)| (&pair[0], &pair[1], cost))
+    }
+}
+
 pub trait Graph<V: Copy + Hash + Eq> {
     fn neighbors(&self, v: &V) -> Vec<Edge<V>>;
 
+    /// A cheapest path from `start` to `end`, or None if `end` cannot be reached. See `a_star`
+    /// for the requirements on the heuristic.
+    fn shortest_path(
+        &self,
+        start: V,
+        end: V,
+        heuristic: Option<&dyn Fn(&V) -> u64>,
+    ) -> Option<Path<V>> {
+        let (_, prev) = self.a_star(start, Some(end), heuristic, true);
+
+        self.reconstruct_path(&prev?, start, end)
+    }
+
+    /// Follow the predecessor map returned by `dijsktra` or `a_star` with `keep_previous` set
+    /// back from `end` to `start`. None if `end` was not reached.
+    fn reconstruct_path(&self, prev: &AHashMap<V, V>, start: V, end: V) -> Option<Path<V>> {
+        let mut vertices = vec![end];
+
+        while vertices[vertices.len() - 1] != start {
+            // A path cannot be longer than the number of vertices with a predecessor, this
+            // guards against maps that do not belong to start
+            if vertices.len() > prev.len() {
+                return None;
+            }
+
+            vertices.push(prev.get(&vertices[vertices.len() - 1]).copied()?);
+        }
+
+        vertices.reverse();
+
+        // The cheapest edge between two consecutive vertices is the one the search took
+        let costs = vertices
+            .windows(2)
+            .map(|pair| {
+                self.neighbors(&pair[0])
+                    .into_iter()
+                    .filter(|e| e.vertex == pair[1])
+                    .map(|e| e.cost)
+                    .min()
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Path { vertices, costs })
+    }
+
     fn dijsktra(
         &self,
         start: V,
         end: Option<V>,
         keep_previous: bool,
     ) -> (AHashMap<V, u64>, Option<AHashMap<V, V>>) {
-        const INF: u64 = std::u64::MAX;
+        self.a_star(start, end, None, keep_previous)
+    }
+
+    /// Like `dijsktra`, but vertices are explored in the order of their distance from `start`
+    /// plus the estimate `heuristic` returns for the remaining distance to `end`. The estimate
+    /// must never exceed the actual distance, otherwise the result might not be optimal.
+    /// Without a heuristic, this is exactly Dijkstra's algorithm.
+    fn a_star(
+        &self,
+        start: V,
+        end: Option<V>,
+        heuristic: Option<&dyn Fn(&V) -> u64>,
+        keep_previous: bool,
+    ) -> (AHashMap<V, u64>, Option<AHashMap<V, V>>) {
+        const INF: u64 = u64::MAX;
 
         // The following code is an only slightly modified version of
         // the implementation found at
@@ -29,6 +120,8 @@ pub trait Graph<V: Copy + Hash + Eq> {
 
         #[derive(Copy, Clone, Eq, PartialEq)]
         struct State<V> {
+            // The cost so far plus the estimated remaining cost
+            priority: u64,
             cost: u64,
             vertex: V,
         }
@@ -39,7 +132,7 @@ pub trait Graph<V: Copy + Hash + Eq> {
                 // Notice that the we flip the ordering on costs.
                 // In case of a tie we compare positions - this step is necessary
                 // to make implementations of `PartialEq` and `Ord` consistent.
-                other.cost.cmp(&self.cost).then_with(|| {
+                other.priority.cmp(&self.priority).then_with(|| {
                     // This may be unconventional, but it works (...to guarantee
                     // duality, which is the hard thing to do here)
                     let mut self_hasher: ahash::AHasher = Default::default();
@@ -60,11 +153,14 @@ pub trait Graph<V: Copy + Hash + Eq> {
             }
         }
 
+        let estimate = |v: &V| heuristic.map_or(0, |h| h(v));
+
         let mut dist: AHashMap<V, u64> = Default::default();
         dist.insert(start, 0);
 
         let mut heap = BinaryHeap::new();
         heap.push(State {
+            priority: estimate(&start),
             cost: 0,
             vertex: start,
         });
@@ -75,7 +171,7 @@ pub trait Graph<V: Copy + Hash + Eq> {
             None
         };
 
-        while let Some(State { cost, vertex }) = heap.pop() {
+        while let Some(State { cost, vertex, .. }) = heap.pop() {
             if Some(vertex) == end {
                 break;
             }
@@ -88,18 +184,19 @@ pub trait Graph<V: Copy + Hash + Eq> {
             // For each node we can reach, see if we can find a way with
             // a lower cost going through this node
             for Edge { vertex: nv, cost: c }in self.neighbors(&vertex) {
-                let next = State {
-                    cost: cost + c,
-                    vertex: nv,
-                };
+                let next_cost = cost + c;
 
-                if next.cost < *dist.get(&next.vertex).unwrap_or(&INF) {
-                    heap.push(next);
+                if next_cost < *dist.get(&nv).unwrap_or(&INF) {
+                    heap.push(State {
+                        priority: next_cost.saturating_add(estimate(&nv)),
+                        cost: next_cost,
+                        vertex: nv,
+                    });
                     // Relaxation, we have now found a better way
-                    dist.insert(next.vertex, next.cost);
+                    dist.insert(nv, next_cost);
 
                     if let Some(prev) = &mut prev {
-                        prev.insert(next.vertex, vertex);
+                        prev.insert(nv, vertex);
                     }
                 }
             }
@@ -107,4 +204,282 @@ pub trait Graph<V: Copy + Hash + Eq> {
 
         (dist, prev)
     }
+
+    /// Like `dijsktra`, but for graphs whose edges all cost 0 or 1. Instead of a priority
+    /// queue, this only needs a deque: vertices reached by free edges go to the front, all
+    /// others to the back. Fails with `InvalidInput` on an edge that costs more than 1.
+    #[allow(clippy::type_complexity)]
+    fn zero_one_bfs(
+        &self,
+        start: V,
+        end: Option<V>,
+        keep_previous: bool,
+    ) -> crate::Result<(AHashMap<V, u64>, Option<AHashMap<V, V>>)> {
+        let mut dist: AHashMap<V, u64> = Default::default();
+        dist.insert(start, 0);
+
+        let mut prev: Option<AHashMap<V, V>> = if keep_previous {
+            Some(Default::default())
+        } else {
+            None
+        };
+
+        let mut queue = VecDeque::new();
+        queue.push_back((0, start));
+
+        while let Some((cost, vertex)) = queue.pop_front() {
+            if Some(vertex) == end {
+                break;
+            }
+
+            // The vertex may have been queued again with a lower cost
+            if cost > dist[&vertex] {
+                continue;
+            }
+
+            for Edge { vertex: nv, cost: c } in self.neighbors(&vertex) {
+                if c > 1 {
+                    return Err(crate::Error::InvalidInput);
+                }
+
+                let next_cost = cost + c;
+
+                if dist.get(&nv).is_none_or(|&d| next_cost < d) {
+                    if c == 0 {
+                        queue.push_front((next_cost, nv));
+                    } else {
+                        queue.push_back((next_cost, nv));
+                    }
+
+                    dist.insert(nv, next_cost);
+
+                    if let Some(prev) = &mut prev {
+                        prev.insert(nv, vertex);
+                    }
+                }
+            }
+        }
+
+        Ok((dist, prev))
+    }
+}
+
+/// A graph whose edges all have the same length, so that it suffices to know which vertices
+/// are adjacent
+pub trait UnweightedGraph<V: Copy + Hash + Eq> {
+    /// Push the vertices adjacent to `v` onto `out`
+    fn adjacent(&self, v: &V, out: &mut Vec<V>);
+
+    /// The number of edges on a shortest path from `start` to every vertex that can be reached
+    fn bfs(&self, start: V) -> AHashMap<V, u64> {
+        let mut dist: AHashMap<V, u64> = Default::default();
+        dist.insert(start, 0);
+
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+
+        let mut adj = Vec::new();
+
+        while let Some(v) = queue.pop_front() {
+            let d = dist[&v] + 1;
+
+            adj.clear();
+            self.adjacent(&v, &mut adj);
+
+            for &w in &adj {
+                if !dist.contains_key(&w) {
+                    dist.insert(w, d);
+                    queue.push_back(w);
+                }
+            }
+        }
+
+        dist
+    }
+
+    /// All vertices that can be reached from `start`, in the order a depth first search
+    /// visits them
+    fn dfs(&self, start: V) -> Vec<V> {
+        let mut visited: AHashSet<V> = Default::default();
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+
+        let mut adj = Vec::new();
+
+        while let Some(v) = stack.pop() {
+            if !visited.insert(v) {
+                continue;
+            }
+
+            order.push(v);
+
+            adj.clear();
+            self.adjacent(&v, &mut adj);
+
+            // Reversed, so that the first adjacent vertex is visited first
+            stack.extend(adj.iter().rev().filter(|w| !visited.contains(w)));
+        }
+
+        order
+    }
+
+    /// Split the given vertices, and everything reachable from them, into connected
+    /// components. This assumes that every edge can be used in both directions.
+    fn connected_components<I: IntoIterator<Item = V>>(&self, vertices: I) -> Vec<Vec<V>>
+    where
+        Self: Sized,
+    {
+        let mut seen: AHashSet<V> = Default::default();
+        let mut components = Vec::new();
+
+        for v in vertices {
+            if seen.contains(&v) {
+                continue;
+            }
+
+            let component = self.dfs(v);
+            seen.extend(component.iter().copied());
+            components.push(component);
+        }
+
+        components
+    }
+}
+
+/// Which implementation of Dijkstra's algorithm to use for a graph that supports both
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// `Graph::dijsktra`, works for any vertex type
+    Hashed,
+    /// `DenseGraph::dense_dijkstra`, faster for graphs with many vertices and small costs
+    Dense,
+}
+
+/// A graph whose vertices can be numbered 0, 1, ..., `vertex_count() - 1`. This allows
+/// keeping the distances in vectors instead of hash maps.
+pub trait DenseGraph<V: Copy + Hash + Eq>: Graph<V> {
+    fn vertex_count(&self) -> usize;
+
+    fn index(&self, v: &V) -> usize;
+
+    fn vertex(&self, i: usize) -> V;
+
+    /// Like `Graph::dijsktra`, but the distances and predecessors are indexed by the vertex
+    /// indices. Unreachable vertices have distance `u64::MAX` and no predecessor. The queue
+    /// has one bucket per distance, so this is only fast if the edge costs are small.
+    fn dense_dijkstra(
+        &self,
+        start: V,
+        end: Option<V>,
+        keep_previous: bool,
+    ) -> (Vec<u64>, Option<Vec<Option<usize>>>) {
+        let n = self.vertex_count();
+
+        let mut dist = vec![u64::MAX; n];
+        let mut prev = if keep_previous { Some(vec![None; n]) } else { None };
+
+        let start = self.index(&start);
+        let end = end.map(|v| self.index(&v));
+
+        dist[start] = 0;
+
+        let mut queue = BucketQueue::new();
+        queue.push(0, start);
+
+        while let Some((cost, i)) = queue.pop() {
+            if Some(i) == end {
+                break;
+            }
+
+            // Important as we may have already found a better way
+            if cost > dist[i] {
+                continue;
+            }
+
+            for Edge { vertex, cost: c } in self.neighbors(&self.vertex(i)) {
+                let j = self.index(&vertex);
+                let next_cost = cost + c;
+
+                if next_cost < dist[j] {
+                    dist[j] = next_cost;
+                    queue.push(next_cost, j);
+
+                    if let Some(prev) = &mut prev {
+                        prev[j] = Some(i);
+                    }
+                }
+            }
+        }
+
+        (dist, prev)
+    }
+
+}
+
+/// A min-priority queue for integer priorities that never decrease below the last popped one,
+/// as is the case in Dijkstra's algorithm. It keeps one bucket for every priority between the
+/// smallest and the largest one in the queue, so it works best if those are close together.
+#[derive(Debug, Clone)]
+pub struct BucketQueue<T> {
+    // The priority of the first bucket
+    base: u64,
+    buckets: VecDeque<Vec<T>>,
+    len: usize,
+}
+
+impl<T> Default for BucketQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> BucketQueue<T> {
+    pub fn new() -> Self {
+        BucketQueue {
+            base: 0,
+            buckets: VecDeque::new(),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Add an item. Panics if the priority is smaller than the one of the last popped item.
+    pub fn push(&mut self, priority: u64, item: T) {
+        assert!(priority >= self.base, "priority below the last popped one");
+
+        let i = (priority - self.base) as usize;
+        if i >= self.buckets.len() {
+            self.buckets.resize_with(i + 1, Vec::new);
+        }
+
+        self.buckets[i].push(item);
+        self.len += 1;
+    }
+
+    /// Remove an item with the smallest priority
+    pub fn pop(&mut self) -> Option<(u64, T)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        loop {
+            if let Some(item) = self.buckets.front_mut().and_then(|b| b.pop()) {
+                self.len -= 1;
+                return Some((self.base, item));
+            }
+
+            // Move on to the next priority, but keep the allocation of the empty bucket
+            if let Some(empty) = self.buckets.pop_front() {
+                self.buckets.push_back(empty);
+            }
+            self.base += 1;
+        }
+    }
 }
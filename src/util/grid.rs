@@ -0,0 +1,388 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use super::{DenseGraph, Edge, Graph};
+
+/// Which of the surrounding cells count as neighbors of a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// Left, right, up and down
+    Four,
+    /// Also the diagonals
+    Eight,
+}
+
+impl Neighborhood {
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Neighborhood::Four => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+            Neighborhood::Eight => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+/// A rectangular grid, stored row by row. Cells are addressed by (x, y), where (0, 0) is the
+/// top left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(width: usize, height: usize, mut f: F) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// A grid of the given width with the cells in row-major order. None if the number of
+    /// cells is not a multiple of the width.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Option<Self> {
+        if width == 0 {
+            return cells.is_empty().then_some(Grid {
+                cells,
+                width: 0,
+                height: 0,
+            });
+        }
+
+        if !cells.len().is_multiple_of(width) {
+            return None;
+        }
+
+        Some(Grid {
+            height: cells.len() / width,
+            width,
+            cells,
+        })
+    }
+
+    /// Parse a map with one character per cell and one line per row. `cell` converts the
+    /// characters, a character it rejects is reported as not being `expected`.
+    pub fn parse<F>(input: &str, expected: &str, cell: F) -> crate::Result<Self>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        Self::parse_lines(input, input.lines(), expected, cell)
+    }
+
+    /// Like `parse`, but the map only consists of the given lines of the input, for inputs
+    /// that contain more than the map. Errors are still reported relative to the whole input.
+    pub fn parse_lines<'a, I, F>(
+        input: &str,
+        lines: I,
+        expected: &str,
+        mut cell: F,
+    ) -> crate::Result<Self>
+    where
+        I: IntoIterator<Item = &'a str>,
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+
+        for line in lines {
+            let line = line.trim();
+
+            if height == 0 {
+                width = line.chars().count();
+            } else if line.chars().count() != width {
+                return Err(crate::Error::parse(
+                    input,
+                    line,
+                    format!("a row of {} cells", width),
+                ));
+            }
+
+            for (i, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    crate::Error::parse(input, &line[i..i + c.len_utf8()], expected)
+                })?;
+
+                cells.push(value);
+            }
+
+            height += 1;
+        }
+
+        if width == 0 {
+            return Err(crate::Error::NoInput);
+        }
+
+        Ok(Grid {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The position (x, y) if it lies within the grid
+    pub fn position(&self, x: isize, y: isize) -> Option<(usize, usize)> {
+        let (x, y) = (usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(x + y * self.width)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(x + y * self.width)
+        } else {
+            None
+        }
+    }
+
+    /// The index of a cell in `cells()`
+    pub fn index_of(&self, (x, y): (usize, usize)) -> usize {
+        x + y * self.width
+    }
+
+    /// The position of the cell with the given index in `cells()`
+    pub fn position_of(&self, i: usize) -> (usize, usize) {
+        (i % self.width, i / self.width)
+    }
+
+    /// All cells in row-major order
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// All positions in row-major order
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+
+        (0..height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    /// The cells of column x from top to bottom. Panics if x is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {} out of bounds", x);
+
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// The neighbors of a position that lie within the grid
+    pub fn neighbors(
+        &self,
+        (x, y): (usize, usize),
+        neighborhood: Neighborhood,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+
+        neighborhood.offsets().iter().filter_map(move |&(dx, dy)| {
+            let nx = x.checked_add_signed(dx).filter(|&nx| nx < width)?;
+            let ny = y.checked_add_signed(dy).filter(|&ny| ny < height)?;
+
+            Some((nx, ny))
+        })
+    }
+
+    /// The neighbors of a position, where leaving the grid on one side enters it on the
+    /// opposite side again. An empty grid has no neighbors to wrap around to.
+    pub fn neighbors_wrapping(
+        &self,
+        (x, y): (usize, usize),
+        neighborhood: Neighborhood,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+
+        let offsets = if width == 0 || height == 0 {
+            &[]
+        } else {
+            neighborhood.offsets()
+        };
+
+        offsets.iter().map(move |&(dx, dy)| {
+            let nx = (x + width).wrapping_add_signed(dx) % width;
+            let ny = (y + height).wrapping_add_signed(dy) % height;
+
+            (nx, ny)
+        })
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Display the grid with one character per cell, as returned by `f`
+    pub fn display_with<'a, F: Fn(&T) -> char + 'a>(&'a self, f: F) -> impl Display + 'a {
+        DisplayWith { grid: self, f }
+    }
+
+    /// The grid as a graph whose vertices are the indices of the cells (see `index_of`).
+    /// Every cell is connected to its neighbors, and moving onto a cell costs what `cost`
+    /// returns for it, or is not possible at all if it returns None.
+    pub fn graph<F: Fn(&T) -> Option<u64>>(
+        &self,
+        neighborhood: Neighborhood,
+        cost: F,
+    ) -> GridGraph<'_, T, F> {
+        GridGraph {
+            grid: self,
+            neighborhood,
+            cost,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        &self.cells[x + y * self.width]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        &mut self.cells[x + y * self.width]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+struct DisplayWith<'a, T, F> {
+    grid: &'a Grid<T>,
+    f: F,
+}
+
+impl<T, F: Fn(&T) -> char> Display for DisplayWith<'_, T, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.grid.rows() {
+            for cell in row {
+                write!(f, "{}", (self.f)(cell))?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A grid seen as a graph, see `Grid::graph`
+pub struct GridGraph<'a, T, F> {
+    pub grid: &'a Grid<T>,
+    neighborhood: Neighborhood,
+    cost: F,
+}
+
+impl<T, F: Fn(&T) -> Option<u64>> Graph<usize> for GridGraph<'_, T, F> {
+    fn neighbors(&self, &v: &usize) -> Vec<Edge<usize>> {
+        let grid = self.grid;
+
+        grid.neighbors(grid.position_of(v), self.neighborhood)
+            .filter_map(|pos| {
+                let cost = (self.cost)(&grid[pos])?;
+
+                Some(Edge {
+                    vertex: grid.index_of(pos),
+                    cost,
+                })
+            })
+            .collect()
+    }
+}
+
+impl<T, F: Fn(&T) -> Option<u64>> DenseGraph<usize> for GridGraph<'_, T, F> {
+    fn vertex_count(&self) -> usize {
+        self.grid.cells.len()
+    }
+
+    fn index(&self, &v: &usize) -> usize {
+        v
+    }
+
+    fn vertex(&self, i: usize) -> usize {
+        i
+    }
+}
@@ -0,0 +1,176 @@
+//! Memoization of recursive functions. A `Memo` caches the values of a function by key, and
+//! the function gets the memo passed back in so that its recursive calls go through the cache
+//! as well:
+//!
+//! ```
+//! use aoc2021::util::Memo;
+//!
+//! fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+//!     if n < 2 {
+//!         n
+//!     } else {
+//!         memo.call(n - 1, &fib) + memo.call(n - 2, &fib)
+//!     }
+//! }
+//!
+//! let mut memo = Memo::new();
+//! assert_eq!(memo.call(90, &fib), 2880067194370816120);
+//! assert_eq!(memo.stats().misses, 91);
+//! ```
+//!
+//! Functions that need more than the key can be wrapped in a closure, e.g.
+//! `memo.call(key, &|memo, key| f(context, memo, key))`.
+
+use std::{fmt::Display, hash::Hash, marker::PhantomData};
+
+use ahash::AHashMap;
+
+/// Where a `Memo` keeps its values
+pub trait Store<K, V> {
+    fn get(&self, key: &K) -> Option<&V>;
+    fn insert(&mut self, key: K, value: V);
+    /// The number of stored values
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K: Hash + Eq, V> Store<K, V> for AHashMap<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        AHashMap::get(self, key)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        AHashMap::insert(self, key, value);
+    }
+
+    fn len(&self) -> usize {
+        std::collections::HashMap::len(self)
+    }
+}
+
+/// A dense store for keys that can be numbered with small indices. The table grows as needed,
+/// so the size passed to `new` is only a hint.
+pub struct Table<K, V> {
+    index: fn(&K) -> usize,
+    values: Vec<Option<V>>,
+    len: usize,
+}
+
+impl<K, V> Table<K, V> {
+    pub fn new(size: usize, index: fn(&K) -> usize) -> Self {
+        Table {
+            index,
+            values: std::iter::repeat_with(|| None).take(size).collect(),
+            len: 0,
+        }
+    }
+}
+
+impl<K, V> Store<K, V> for Table<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        self.values.get((self.index)(key))?.as_ref()
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        let i = (self.index)(&key);
+
+        if i >= self.values.len() {
+            self.values.resize_with(i + 1, || None);
+        }
+
+        if self.values[i].replace(value).is_none() {
+            self.len += 1;
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// How often a `Memo` could answer from its cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} hits, {} misses", self.hits, self.misses)
+    }
+}
+
+/// A cache for the values of a (recursive) function, see the module documentation
+pub struct Memo<K, V, S = AHashMap<K, V>> {
+    store: S,
+    stats: Stats,
+    _marker: PhantomData<fn(K) -> V>,
+}
+
+impl<K: Hash + Eq, V> Memo<K, V> {
+    /// A memo backed by a hash map
+    pub fn new() -> Self {
+        Self::with_store(AHashMap::default())
+    }
+}
+
+impl<K: Hash + Eq, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Memo<K, V, Table<K, V>> {
+    /// A memo backed by a `Table` of the given size
+    pub fn table(size: usize, index: fn(&K) -> usize) -> Self {
+        Self::with_store(Table::new(size, index))
+    }
+}
+
+impl<K, V, S: Store<K, V>> Memo<K, V, S> {
+    pub fn with_store(store: S) -> Self {
+        Memo {
+            store,
+            stats: Stats::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The value of `f` at `key`, computed only if it is not cached yet. `f` gets this memo
+    /// passed, to make its recursive calls through it.
+    pub fn call<F>(&mut self, key: K, f: &F) -> V
+    where
+        K: Clone,
+        V: Clone,
+        F: Fn(&mut Self, K) -> V,
+    {
+        if let Some(value) = self.store.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+
+        let value = f(self, key.clone());
+        self.store.insert(key, value.clone());
+
+        value
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// The number of cached values
+    pub fn len(&self) -> usize {
+        self.store.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.store.is_empty()
+    }
+}
@@ -0,0 +1,239 @@
+//! Small parser combinators for puzzle inputs. A parser is a function from the remaining input
+//! to a `Parsed`: either the parsed value and what is left of the input, or the position where
+//! parsing failed and what was expected there. Since the remaining input is always a slice of
+//! the original one, `parse_all` can turn a failure into an error with line and column.
+
+use std::str::FromStr;
+
+use crate::error::{failed_at, Parsed};
+
+/// Anything that can be used as a parser, in particular functions `fn(&str) -> Parsed<T>`
+pub trait Parser<'a, T>: Fn(&'a str) -> Parsed<'a, T> {}
+
+impl<'a, T, F: Fn(&'a str) -> Parsed<'a, T>> Parser<'a, T> for F {}
+
+/// Run the parser on `s`, which has to be consumed entirely. Errors are reported relative to
+/// `input`, of which `s` has to be a part.
+pub fn parse_all<'a, T>(input: &str, s: &'a str, parser: impl Parser<'a, T>) -> crate::Result<T> {
+    match parser(s) {
+        Ok((value, "")) => Ok(value),
+        Ok((_, rem)) => Err(failed_at(input, (rem, "the end of the line"))),
+        Err(e) => Err(failed_at(input, e)),
+    }
+}
+
+/// Succeeds only if there is no input left
+pub fn end(s: &str) -> Parsed<'_, ()> {
+    if s.is_empty() {
+        Ok(((), s))
+    } else {
+        Err((s, "the end of the input"))
+    }
+}
+
+/// Exactly the given text
+pub fn literal<'a>(lit: &'static str) -> impl Parser<'a, &'a str> {
+    move |s: &'a str| match s.strip_prefix(lit) {
+        Some(rem) => Ok((&s[..lit.len()], rem)),
+        None => Err((s, lit)),
+    }
+}
+
+/// A (possibly negative) decimal number
+pub fn integer<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |s: &'a str| {
+        let sign = usize::from(s.starts_with('-'));
+        let len = sign + s[sign..].bytes().take_while(u8::is_ascii_digit).count();
+
+        match s[..len].parse() {
+            Ok(value) => Ok((value, &s[len..])),
+            Err(_) => Err((s, "a number")),
+        }
+    }
+}
+
+/// A single decimal digit
+pub fn digit(s: &str) -> Parsed<'_, u32> {
+    match s.chars().next().and_then(|c| c.to_digit(10)) {
+        Some(d) => Ok((d, &s[1..])),
+        None => Err((s, "a digit")),
+    }
+}
+
+/// The next `n` characters
+pub fn take<'a>(n: usize) -> impl Parser<'a, &'a str> {
+    move |s: &'a str| match s.char_indices().nth(n) {
+        Some((i, _)) => Ok((&s[..i], &s[i..])),
+        None if s.chars().count() == n => Ok((s, &s[s.len()..])),
+        None => Err((s, "more characters")),
+    }
+}
+
+pub fn map<'a, A, B>(parser: impl Parser<'a, A>, f: impl Fn(A) -> B) -> impl Parser<'a, B> {
+    move |s: &'a str| parser(s).map(|(value, rem)| (f(value), rem))
+}
+
+/// Like `map`, but `f` may reject the value, which is then reported as not being `expected`
+pub fn try_map<'a, A, B>(
+    parser: impl Parser<'a, A>,
+    expected: &'static str,
+    f: impl Fn(A) -> Option<B>,
+) -> impl Parser<'a, B> {
+    move |s: &'a str| {
+        let (value, rem) = parser(s)?;
+
+        f(value).map(|value| (value, rem)).ok_or((s, expected))
+    }
+}
+
+/// Both parsers one after the other
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |s: &'a str| {
+        let (a, rem) = first(s)?;
+        let (b, rem) = second(rem)?;
+
+        Ok(((a, b), rem))
+    }
+}
+
+/// Both parsers one after the other, keeping only the value of the second one
+pub fn preceded<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, B> {
+    map(pair(first, second), |(_, b)| b)
+}
+
+/// Both parsers one after the other, keeping only the value of the first one
+pub fn terminated<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, A> {
+    map(pair(first, second), |(a, _)| a)
+}
+
+/// The value of `inner`, between `open` and `close`
+pub fn delimited<'a, A, B, C>(
+    open: impl Parser<'a, A>,
+    inner: impl Parser<'a, B>,
+    close: impl Parser<'a, C>,
+) -> impl Parser<'a, B> {
+    preceded(open, terminated(inner, close))
+}
+
+/// The first parser, or if it fails, the second one. If both fail, the failure of the one that
+/// got further is reported.
+pub fn alt<'a, T>(first: impl Parser<'a, T>, second: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |s: &'a str| match first(s) {
+        Ok(res) => Ok(res),
+        Err(e1) => match second(s) {
+            Ok(res) => Ok(res),
+            Err(e2) if e1.0.len() < e2.0.len() => Err(e1),
+            Err(e2) => Err(e2),
+        },
+    }
+}
+
+/// One of the given keywords, mapped to its value. The first matching keyword wins.
+pub fn one_of<'a, T: Copy>(
+    options: &'static [(&'static str, T)],
+    expected: &'static str,
+) -> impl Parser<'a, T> {
+    move |s: &'a str| {
+        options
+            .iter()
+            .find_map(|&(keyword, value)| Some((value, s.strip_prefix(keyword)?)))
+            .ok_or((s, expected))
+    }
+}
+
+/// Report a failure of `parser` at its start as not being `expected`, for a better
+/// description than the one of whatever part of it failed. Failures further in are kept.
+pub fn label<'a, T>(parser: impl Parser<'a, T>, expected: &'static str) -> impl Parser<'a, T> {
+    move |s: &'a str| match parser(s) {
+        Err((at, _)) if at.len() == s.len() => Err((s, expected)),
+        res => res,
+    }
+}
+
+/// One or more values separated by `separator`
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |s: &'a str| {
+        let (first, mut rem) = item(s)?;
+        let mut items = vec![first];
+
+        while let Ok((_, after_separator)) = separator(rem) {
+            let (next, after_item) = item(after_separator)?;
+
+            items.push(next);
+            rem = after_item;
+        }
+
+        Ok((items, rem))
+    }
+}
+
+/// As many values as possible, possibly none
+pub fn many<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |s: &'a str| {
+        let mut items = Vec::new();
+        let mut rem = s;
+
+        while let Ok((next, after_item)) = item(rem) {
+            // Stop at parsers that succeed without consuming anything
+            if after_item.len() == rem.len() {
+                break;
+            }
+
+            items.push(next);
+            rem = after_item;
+        }
+
+        Ok((items, rem))
+    }
+}
+
+/// As many values as possible, but at least one
+pub fn many1<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |s: &'a str| {
+        let (first, rem) = item(s)?;
+        let (mut rest, rem) = many(&item)(rem)?;
+
+        rest.insert(0, first);
+
+        Ok((rest, rem))
+    }
+}
+
+/// Exactly `n` values
+pub fn count<'a, T>(n: usize, item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |s: &'a str| {
+        let mut items = Vec::with_capacity(n);
+        let mut rem = s;
+
+        for _ in 0..n {
+            let (next, after_item) = item(rem)?;
+
+            items.push(next);
+            rem = after_item;
+        }
+
+        Ok((items, rem))
+    }
+}
+
+/// The value together with the part of the input it was parsed from, to report errors that
+/// are only detected later
+pub fn spanned<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, (T, &'a str)> {
+    move |s: &'a str| {
+        let (value, rem) = parser(s)?;
+
+        Ok(((value, &s[..s.len() - rem.len()]), rem))
+    }
+}
@@ -0,0 +1,400 @@
+//! Two- and three-dimensional vectors of integers, for points and directions
+
+use std::{
+    fmt::{Debug, Display},
+    iter::Sum,
+    ops::{
+        Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Rem, Sub, SubAssign,
+    },
+};
+
+/// The integer types that can be components of a vector
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// The absolute value, the identity for unsigned types
+    fn abs(self) -> Self;
+    /// -1, 0 or 1 depending on the sign
+    fn signum(self) -> Self;
+}
+
+macro_rules! impl_integer {
+    (signed: $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+
+                fn signum(self) -> Self {
+                    <$t>::signum(self)
+                }
+            }
+        )*
+    };
+    (unsigned: $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn abs(self) -> Self {
+                    self
+                }
+
+                fn signum(self) -> Self {
+                    (self != 0) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(signed: i8, i16, i32, i64, isize);
+impl_integer!(unsigned: u8, u16, u32, u64, usize);
+
+/// The component-wise operations on vectors that do not depend on their dimension
+pub trait Vector: Copy + Add<Output = Self> + Sub<Output = Self> {
+    type Scalar: Integer;
+
+    /// The vector with all components set to `k`
+    fn splat(k: Self::Scalar) -> Self;
+    /// The component-wise minimum
+    fn min(self, other: Self) -> Self;
+    /// The component-wise maximum
+    fn max(self, other: Self) -> Self;
+    /// Whether every component is at most the one of `other`
+    fn all_le(self, other: Self) -> bool;
+}
+
+/// Everything that `Vec2` and `Vec3` have in common, generated for the given component names
+macro_rules! vector {
+    ($name:ident, $n:literal, $($c:ident),*) => {
+        impl<T> $name<T> {
+            pub const fn new($($c: T),*) -> Self {
+                $name { $($c),* }
+            }
+
+            /// Apply `f` to every component
+            pub fn map<U, F: FnMut(T) -> U>(self, mut f: F) -> $name<U> {
+                $name { $($c: f(self.$c)),* }
+            }
+
+            pub fn to_array(self) -> [T; $n] {
+                [$(self.$c),*]
+            }
+        }
+
+        impl<T: Integer> $name<T> {
+            pub const ZERO: Self = $name { $($c: T::ZERO),* };
+
+            /// The sum of the absolute values of the components
+            pub fn manhattan(self) -> T {
+                T::ZERO $(+ self.$c.abs())*
+            }
+
+            /// The biggest absolute value of the components
+            pub fn chebyshev(self) -> T {
+                T::ZERO $(.max(self.$c.abs()))*
+            }
+
+            /// Every component replaced by its sign, so a step in the direction of the vector
+            /// in a grid that allows diagonal steps
+            pub fn signum(self) -> Self {
+                self.map(T::signum)
+            }
+
+            pub fn dot(self, other: Self) -> T {
+                T::ZERO $(+ self.$c * other.$c)*
+            }
+        }
+
+        impl<T: Display> Display for $name<T> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let components = [$(self.$c.to_string()),*];
+
+                write!(f, "{}", components.join(","))
+            }
+        }
+
+        impl<T> From<[T; $n]> for $name<T> {
+            fn from([$($c),*]: [T; $n]) -> Self {
+                $name { $($c),* }
+            }
+        }
+
+        impl<T: Integer> Add for $name<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                $name { $($c: self.$c + other.$c),* }
+            }
+        }
+
+        impl<T: Integer> Sub for $name<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                $name { $($c: self.$c - other.$c),* }
+            }
+        }
+
+        impl<T: Integer + Neg<Output = T>> Neg for $name<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $name { $($c: -self.$c),* }
+            }
+        }
+
+        impl<T: Integer> Mul<T> for $name<T> {
+            type Output = Self;
+
+            fn mul(self, k: T) -> Self {
+                $name { $($c: self.$c * k),* }
+            }
+        }
+
+        impl<T: Integer> Div<T> for $name<T> {
+            type Output = Self;
+
+            fn div(self, k: T) -> Self {
+                $name { $($c: self.$c / k),* }
+            }
+        }
+
+        impl<T: Integer> Rem<T> for $name<T> {
+            type Output = Self;
+
+            fn rem(self, k: T) -> Self {
+                $name { $($c: self.$c % k),* }
+            }
+        }
+
+        impl<T: Integer> AddAssign for $name<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Integer> SubAssign for $name<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T: Integer> MulAssign<T> for $name<T> {
+            fn mul_assign(&mut self, k: T) {
+                *self = *self * k;
+            }
+        }
+
+        impl<T: Integer> DivAssign<T> for $name<T> {
+            fn div_assign(&mut self, k: T) {
+                *self = *self / k;
+            }
+        }
+
+        impl<T: Integer> Sum for $name<T> {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, Add::add)
+            }
+        }
+
+        impl<T> Index<usize> for $name<T> {
+            type Output = T;
+
+            fn index(&self, i: usize) -> &T {
+                [$(&self.$c),*][i]
+            }
+        }
+
+        impl<T> IndexMut<usize> for $name<T> {
+            fn index_mut(&mut self, i: usize) -> &mut T {
+                [$(&mut self.$c),*].into_iter().nth(i).expect("component out of bounds")
+            }
+        }
+
+        impl<T: Integer> Vector for $name<T> {
+            type Scalar = T;
+
+            fn splat(k: T) -> Self {
+                $name { $($c: k),* }
+            }
+
+            fn min(self, other: Self) -> Self {
+                $name { $($c: self.$c.min(other.$c)),* }
+            }
+
+            fn max(self, other: Self) -> Self {
+                $name { $($c: self.$c.max(other.$c)),* }
+            }
+
+            fn all_le(self, other: Self) -> bool {
+                true $(&& self.$c <= other.$c)*
+            }
+        }
+    };
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// The points between `min` and `max`, both inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox<V> {
+    pub min: V,
+    pub max: V,
+}
+
+vector!(Vec2, 2, x, y);
+vector!(Vec3, 3, x, y, z);
+
+impl<V: Vector> BoundingBox<V> {
+    /// The smallest box that contains all points, None if there are none
+    pub fn of<I: IntoIterator<Item = V>>(points: I) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        let mut bounds = BoundingBox {
+            min: first,
+            max: first,
+        };
+        bounds.extend(points);
+
+        Some(bounds)
+    }
+
+    /// Grow the box to contain the points
+    pub fn extend<I: IntoIterator<Item = V>>(&mut self, points: I) {
+        for p in points {
+            self.min = self.min.min(p);
+            self.max = self.max.max(p);
+        }
+    }
+
+    pub fn contains(&self, p: V) -> bool {
+        self.min.all_le(p) && p.all_le(self.max)
+    }
+
+    /// The number of points along each axis
+    pub fn size(&self) -> V {
+        let one = V::splat(<V::Scalar as Integer>::ONE);
+
+        (self.max - self.min + one).max(V::splat(<V::Scalar as Integer>::ZERO))
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Vec3 { x, y, z }
+    }
+}
+
+/// One of the 24 rotations that map a cube onto itself. Component i of a rotated vector is
+/// component `permutation[i]` of the original one, times `signs[i]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation {
+    permutation: [usize; 3],
+    signs: [i8; 3],
+}
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation {
+        permutation: [0, 1, 2],
+        signs: [1, 1, 1],
+    };
+
+    /// All 24 rotations, starting with the identity
+    pub fn all() -> impl Iterator<Item = Rotation> {
+        // The permutations of the axes, with their sign as a permutation
+        const PERMUTATIONS: [(i8, [usize; 3]); 6] = [
+            (1, [0, 1, 2]),
+            (1, [1, 2, 0]),
+            (1, [2, 0, 1]),
+            (-1, [1, 0, 2]),
+            (-1, [0, 2, 1]),
+            (-1, [2, 1, 0]),
+        ];
+
+        // Of the 48 signed permutations, the rotations are the ones that do not mirror, i.e.
+        // whose determinant is 1
+        PERMUTATIONS.into_iter().flat_map(|(sign, permutation)| {
+            (0..8)
+                .map(|bits| [1, 2, 4].map(|bit| if bits & bit == 0 { 1 } else { -1 }))
+                .filter(move |signs: &[i8; 3]| sign * signs[0] * signs[1] * signs[2] == 1)
+                .map(move |signs| Rotation { permutation, signs })
+        })
+    }
+
+    pub fn apply<T: Integer + Neg<Output = T>>(self, v: Vec3<T>) -> Vec3<T> {
+        let c = |i: usize| {
+            let x = v[self.permutation[i]];
+
+            if self.signs[i] < 0 {
+                -x
+            } else {
+                x
+            }
+        };
+
+        Vec3::new(c(0), c(1), c(2))
+    }
+
+    /// The rotation that undoes this one
+    pub fn inverse(self) -> Rotation {
+        let mut inverse = Rotation::IDENTITY;
+
+        for i in 0..3 {
+            inverse.permutation[self.permutation[i]] = i;
+            inverse.signs[self.permutation[i]] = self.signs[i];
+        }
+
+        inverse
+    }
+
+    /// The rotation that first applies this one and then `other`
+    pub fn then(self, other: Rotation) -> Rotation {
+        Rotation {
+            permutation: other.permutation.map(|j| self.permutation[j]),
+            signs: [0, 1, 2].map(|i| other.signs[i] * self.signs[other.permutation[i]]),
+        }
+    }
+}
+
+impl<T: Integer + Neg<Output = T>> Mul<Vec3<T>> for Rotation {
+    type Output = Vec3<T>;
+
+    fn mul(self, v: Vec3<T>) -> Vec3<T> {
+        self.apply(v)
+    }
+}
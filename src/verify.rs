@@ -0,0 +1,104 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{solution::Answer, solvers::Part};
+
+/// The known answers of each day and part, as read from an answers file like problems/answers
+#[derive(Debug, Clone, Default)]
+pub struct ExpectedAnswers {
+    answers: HashMap<(u8, Part), String>,
+}
+
+impl ExpectedAnswers {
+    /// Parse an answers file. Every entry is `<day>.<part>: <answer>`, multi-line answers
+    /// start on the line after the colon and have every row indented by four spaces.
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn parse(s: &str) -> crate::Result<Self> {
+        let mut answers = HashMap::new();
+        let mut current: Option<(u8, Part)> = None;
+
+        for line in s.lines() {
+            if let Some(row) = line.strip_prefix("    ") {
+                let key = current.ok_or(crate::Error::InvalidInput)?;
+                let answer: &mut String = answers.entry(key).or_default();
+
+                if !answer.is_empty() {
+                    answer.push('\n');
+                }
+
+                answer.push_str(row);
+                continue;
+            }
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, answer) = line.split_once(':').ok_or(crate::Error::InvalidInput)?;
+            let (day, part) = key.split_once('.').ok_or(crate::Error::InvalidInput)?;
+
+            let day = day.trim().parse::<u8>()?;
+            let part = match part.trim() {
+                "1" => Part::One,
+                "2" => Part::Two,
+                _ => return Err(crate::Error::InvalidInput),
+            };
+
+            answers.insert((day, part), answer.trim().to_owned());
+            current = Some((day, part));
+        }
+
+        Ok(ExpectedAnswers { answers })
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, answer: &Answer) {
+        self.answers.insert((day, part), answer.to_string());
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(|s| s.as_str())
+    }
+
+    /// Compare an answer to the expected one
+    pub fn check(&self, day: u8, part: Part, answer: &Answer) -> Outcome {
+        match self.get(day, part) {
+            Some(expected) if expected == answer.to_string() => Outcome::Pass,
+            Some(expected) => Outcome::Fail {
+                expected: expected.to_owned(),
+            },
+            None => Outcome::Missing,
+        }
+    }
+}
+
+/// The answers in the format that `parse` reads, ordered by day and part
+impl Display for ExpectedAnswers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut keys: Vec<_> = self.answers.keys().collect();
+        keys.sort_by_key(|(day, part)| (*day, part.number()));
+
+        for key @ (day, part) in keys {
+            let answer = &self.answers[key];
+
+            if answer.contains('\n') {
+                writeln!(f, "{}.{}:", day, part.number())?;
+
+                for row in answer.lines() {
+                    writeln!(f, "    {}", row)?;
+                }
+            } else {
+                writeln!(f, "{}.{}: {}", day, part.number(), answer)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail { expected: String },
+    /// There is no expected answer for this part
+    Missing,
+}
@@ -0,0 +1,341 @@
+//! The search algorithms of `util` on small graphs whose answers are known, and on random
+//! graphs where they have to agree with each other.
+
+use aoc2021::{
+    day15::{self, Day15},
+    generate::{self, Rng},
+    solvers::Part,
+    util::{Backend, BucketQueue, DenseGraph, Edge, Graph, Grid, Neighborhood, UnweightedGraph},
+    Error, Solution,
+};
+
+/// A directed graph on the vertices 0, 1, ..., n - 1, given as the outgoing edges of every
+/// vertex
+struct Edges(Vec<Vec<(usize, u64)>>);
+
+impl Graph<usize> for Edges {
+    fn neighbors(&self, &v: &usize) -> Vec<Edge<usize>> {
+        self.0[v]
+            .iter()
+            .map(|&(vertex, cost)| Edge { vertex, cost })
+            .collect()
+    }
+}
+
+impl UnweightedGraph<usize> for Edges {
+    fn adjacent(&self, &v: &usize, out: &mut Vec<usize>) {
+        out.extend(self.0[v].iter().map(|&(w, _)| w));
+    }
+}
+
+impl DenseGraph<usize> for Edges {
+    fn vertex_count(&self) -> usize {
+        self.0.len()
+    }
+
+    fn index(&self, &v: &usize) -> usize {
+        v
+    }
+
+    fn vertex(&self, i: usize) -> usize {
+        i
+    }
+}
+
+/// A graph with `n` vertices and about `3 * n` edges, with costs from 0 to `max_cost`
+fn random_graph(rng: &mut Rng, n: usize, max_cost: u64) -> Edges {
+    Edges(
+        (0..n)
+            .map(|_| {
+                (0..rng.below(7))
+                    .map(|_| (rng.below(n as u64) as usize, rng.below(max_cost + 1)))
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+/// A square map with risk levels from 1 to 9 like on day 15, but where a cell of 0 is a wall
+fn random_map(rng: &mut Rng, size: usize) -> Grid<u8> {
+    Grid::from_fn(size, size, |_, _| {
+        if rng.chance(0.3) {
+            0
+        } else {
+            1 + rng.below(9) as u8
+        }
+    })
+}
+
+fn risk(&level: &u8) -> Option<u64> {
+    (level > 0).then_some(level as u64)
+}
+
+/// The Manhattan distance to the bottom right corner, a lower bound as every step costs 1 or
+/// more
+fn manhattan(map: &Grid<u8>) -> impl Fn(&usize) -> u64 + '_ {
+    let end = map.position_of(map.cells().len() - 1);
+
+    move |&v| {
+        let (x, y) = map.position_of(v);
+        (x.abs_diff(end.0) + y.abs_diff(end.1)) as u64
+    }
+}
+
+#[test]
+fn a_star_agrees_with_dijkstra() {
+    let mut rng = Rng::new(12);
+    let mut unreachable = 0;
+
+    for _ in 0..200 {
+        let size = 1 + rng.below(12) as usize;
+        let map = random_map(&mut rng, size);
+        let graph = map.graph(Neighborhood::Four, risk);
+        let end = map.cells().len() - 1;
+
+        let (expected, _) = graph.dijsktra(0, Some(end), false);
+        let (dist, _) = graph.a_star(0, Some(end), Some(&manhattan(&map)), false);
+
+        assert_eq!(dist.get(&end), expected.get(&end));
+
+        if !dist.contains_key(&end) {
+            unreachable += 1;
+        }
+    }
+
+    // Both outcomes are covered
+    assert!(unreachable > 0 && unreachable < 200);
+}
+
+#[test]
+fn a_star_does_not_reach_walled_off_vertices() {
+    let map = Grid::parse("1101\n1101\n1101\n", "a digit", |c| {
+        c.to_digit(10).map(|d| d as u8)
+    })
+    .unwrap();
+    let graph = map.graph(Neighborhood::Eight, risk);
+    let end = map.cells().len() - 1;
+
+    let (dist, _) = graph.a_star(0, Some(end), Some(&manhattan(&map)), false);
+
+    assert_eq!(dist.get(&end), None);
+    assert_eq!(dist.len(), 6);
+}
+
+#[test]
+fn shortest_paths_cost_their_distance() {
+    let mut rng = Rng::new(13);
+
+    for _ in 0..200 {
+        let size = 1 + rng.below(12) as usize;
+        let map = random_map(&mut rng, size);
+        let graph = map.graph(Neighborhood::Four, risk);
+        let end = map.cells().len() - 1;
+
+        let (dist, _) = graph.dijsktra(0, Some(end), false);
+
+        for heuristic in [None, Some(&manhattan(&map) as &dyn Fn(&usize) -> u64)] {
+            let Some(path) = graph.shortest_path(0, end, heuristic) else {
+                assert!(!dist.contains_key(&end));
+                continue;
+            };
+
+            assert_eq!(path.vertices.first(), Some(&0));
+            assert_eq!(path.vertices.last(), Some(&end));
+            assert_eq!(path.costs.len() + 1, path.vertices.len());
+            assert_eq!(path.cost(), dist[&end]);
+
+            // Every step is an edge of the graph with the cost given for it
+            for (&from, &to, cost) in path.edges() {
+                assert!(graph
+                    .neighbors(&from)
+                    .iter()
+                    .any(|e| e.vertex == to && e.cost == cost));
+            }
+        }
+    }
+}
+
+#[test]
+fn reconstruct_path() {
+    // 0 -> 1 -> 3 costs 2 + 2, 0 -> 2 -> 3 costs 1 + 5, and 4 is not reached
+    let graph = Edges(vec![
+        vec![(1, 2), (2, 1)],
+        vec![(3, 2)],
+        vec![(3, 5)],
+        vec![],
+        vec![(0, 1)],
+    ]);
+
+    let (dist, prev) = graph.dijsktra(0, None, true);
+    let prev = prev.unwrap();
+
+    let path = graph.reconstruct_path(&prev, 0, 3).unwrap();
+    assert_eq!(path.vertices, vec![0, 1, 3]);
+    assert_eq!(path.costs, vec![2, 2]);
+    assert_eq!(path.cost(), dist[&3]);
+
+    // The path to the start itself has no edges
+    let path = graph.reconstruct_path(&prev, 0, 0).unwrap();
+    assert_eq!((path.cost(), path.vertices), (0, vec![0]));
+
+    assert_eq!(graph.reconstruct_path(&prev, 0, 4), None);
+    assert_eq!(graph.shortest_path(0, 4, None), None);
+
+    // A predecessor map that does not lead back to the given start
+    assert_eq!(graph.reconstruct_path(&prev, 4, 3), None);
+}
+
+#[test]
+fn zero_one_bfs_agrees_with_dijkstra() {
+    let mut rng = Rng::new(15);
+
+    for _ in 0..200 {
+        let n = 1 + rng.below(30) as usize;
+        let graph = random_graph(&mut rng, n, 1);
+
+        let (expected, _) = graph.dijsktra(0, None, false);
+        let (dist, prev) = graph.zero_one_bfs(0, None, true).unwrap();
+
+        assert_eq!(dist, expected);
+
+        // Every reached vertex has a path back to the start, which costs its distance
+        let prev = prev.unwrap();
+        for (&v, &d) in &dist {
+            let path = graph.reconstruct_path(&prev, 0, v).unwrap();
+            assert_eq!(path.cost(), d);
+        }
+    }
+}
+
+#[test]
+fn zero_one_bfs_rejects_expensive_edges() {
+    let graph = Edges(vec![vec![(1, 0)], vec![(2, 2)], vec![]]);
+
+    assert!(matches!(
+        graph.zero_one_bfs(0, None, false),
+        Err(Error::InvalidInput)
+    ));
+}
+
+#[test]
+fn connected_components() {
+    // 0 - 1 - 2, 3 - 4 and 5 on its own, with edges in both directions
+    let graph = Edges(vec![
+        vec![(1, 1)],
+        vec![(0, 1), (2, 1)],
+        vec![(1, 1)],
+        vec![(4, 1)],
+        vec![(3, 1)],
+        vec![],
+    ]);
+
+    let mut components = graph.connected_components([2, 0, 4, 5]);
+    for component in &mut components {
+        component.sort();
+    }
+
+    assert_eq!(components, vec![vec![0, 1, 2], vec![3, 4], vec![5]]);
+
+    // Vertices reachable from the given ones are included, the others are not
+    assert_eq!(graph.connected_components([3]), vec![vec![3, 4]]);
+    assert!(graph.connected_components([]).is_empty());
+}
+
+#[test]
+fn dense_dijkstra_agrees_with_dijkstra() {
+    let mut rng = Rng::new(14);
+
+    for _ in 0..200 {
+        let n = 1 + rng.below(30) as usize;
+        let graph = random_graph(&mut rng, n, 9);
+
+        let (expected, _) = graph.dijsktra(0, None, false);
+        let (dist, prev) = graph.dense_dijkstra(0, None, true);
+        let prev = prev.unwrap();
+
+        for (v, &d) in dist.iter().enumerate() {
+            assert_eq!(
+                Some(d).filter(|&d| d != u64::MAX),
+                expected.get(&v).copied()
+            );
+
+            if d == u64::MAX {
+                continue;
+            }
+
+            // Following the predecessors back from a reached vertex ends at the start, over
+            // edges that cost its distance in total
+            let (mut w, mut cost) = (v, 0);
+            while let Some(p) = prev[w] {
+                cost += graph.0[p]
+                    .iter()
+                    .filter(|&&(x, _)| x == w)
+                    .map(|&(_, c)| c)
+                    .min()
+                    .unwrap();
+                w = p;
+            }
+
+            assert_eq!((w, cost), (0, d));
+        }
+    }
+}
+
+#[test]
+fn day15_backends_agree() {
+    let example = "\
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581
+";
+
+    let inputs = (0..2).map(|seed| generate::generate(15, seed).unwrap().input);
+
+    for input in std::iter::once(example.to_owned()).chain(inputs) {
+        let map = Day15::parse(&input).unwrap();
+
+        for part in [Part::One, Part::Two] {
+            let hashed = day15::lowest_risk(&map, part, Backend::Hashed).unwrap();
+            let dense = day15::lowest_risk(&map, part, Backend::Dense).unwrap();
+
+            assert_eq!(hashed, dense, "part {}", part.number());
+        }
+    }
+
+    let map = Day15::parse(example).unwrap();
+    assert_eq!(
+        day15::lowest_risk(&map, Part::One, Backend::Hashed).unwrap(),
+        40
+    );
+    assert_eq!(
+        day15::lowest_risk(&map, Part::Two, Backend::Hashed).unwrap(),
+        315
+    );
+}
+
+#[test]
+fn bucket_queue_pops_by_priority() {
+    let mut queue = BucketQueue::new();
+
+    for (priority, item) in [(3, 'a'), (1, 'b'), (7, 'c'), (1, 'd')] {
+        queue.push(priority, item);
+    }
+    assert_eq!(queue.len(), 4);
+
+    let (priority, _) = queue.pop().unwrap();
+    assert_eq!(priority, 1);
+
+    // Pushing at the priority popped last is allowed
+    queue.push(1, 'e');
+
+    let popped: Vec<u64> = std::iter::from_fn(|| queue.pop()).map(|(p, _)| p).collect();
+    assert_eq!(popped, vec![1, 1, 3, 7]);
+    assert!(queue.is_empty());
+}
@@ -0,0 +1,190 @@
+//! `util::Grid`: parsing character maps, the neighbors of cells, row and column views and how
+//! grids are displayed
+
+use aoc2021::{
+    error::ParseError,
+    util::{Grid, Neighborhood},
+    Error,
+};
+
+fn digit(c: char) -> Option<u8> {
+    c.to_digit(10).map(|d| d as u8)
+}
+
+/// The position, the offending text and what was expected instead
+fn parse_error(result: aoc2021::Result<Grid<u8>>) -> (usize, usize, String, String) {
+    match result {
+        Err(Error::Parse(ParseError {
+            line,
+            column,
+            found,
+            expected,
+            ..
+        })) => (line, column, found, expected),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
+
+fn error(
+    line: usize,
+    column: usize,
+    found: &str,
+    expected: &str,
+) -> (usize, usize, String, String) {
+    (line, column, found.to_owned(), expected.to_owned())
+}
+
+#[test]
+fn parse() {
+    let grid = Grid::parse("123\n456\n", "a digit", digit).unwrap();
+
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid.cells(), &[1, 2, 3, 4, 5, 6]);
+    assert_eq!(grid[(2, 1)], 6);
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(
+        parse_error(Grid::parse("123\n45\n", "a digit", digit)),
+        error(2, 1, "45", "a row of 3 cells")
+    );
+    assert_eq!(
+        parse_error(Grid::parse("123\n4x6\n", "a digit", digit)),
+        error(2, 2, "x", "a digit")
+    );
+    assert!(matches!(
+        Grid::parse("", "a digit", digit),
+        Err(Error::NoInput)
+    ));
+
+    // Positions are relative to the whole input, and counted in characters
+    let input = "a header\n\n12\né3\n";
+    assert_eq!(
+        parse_error(Grid::parse_lines(
+            input,
+            input.lines().skip(2),
+            "a digit",
+            digit
+        )),
+        error(4, 1, "é", "a digit")
+    );
+
+    let input = "a header\n\n12\n3é\n";
+    assert_eq!(
+        parse_error(Grid::parse_lines(
+            input,
+            input.lines().skip(2),
+            "a digit",
+            digit
+        )),
+        error(4, 2, "é", "a digit")
+    );
+}
+
+#[test]
+fn neighbors() {
+    let grid = Grid::new(3, 2, 0u8);
+
+    let mut neighbors: Vec<_> = grid.neighbors((0, 0), Neighborhood::Four).collect();
+    neighbors.sort();
+    assert_eq!(neighbors, vec![(0, 1), (1, 0)]);
+
+    assert_eq!(grid.neighbors((1, 0), Neighborhood::Eight).count(), 5);
+}
+
+#[test]
+fn neighbors_wrapping() {
+    let grid = Grid::new(3, 4, 0u8);
+
+    let mut neighbors: Vec<_> = grid
+        .neighbors_wrapping((0, 0), Neighborhood::Four)
+        .collect();
+    neighbors.sort();
+    assert_eq!(neighbors, vec![(0, 1), (0, 3), (1, 0), (2, 0)]);
+
+    let mut neighbors: Vec<_> = grid
+        .neighbors_wrapping((2, 3), Neighborhood::Eight)
+        .collect();
+    neighbors.sort();
+    assert_eq!(
+        neighbors,
+        vec![
+            (0, 0),
+            (0, 2),
+            (0, 3),
+            (1, 0),
+            (1, 2),
+            (1, 3),
+            (2, 0),
+            (2, 2)
+        ]
+    );
+
+    // In a single cell every neighbor is the cell itself
+    let grid = Grid::new(1, 1, 0u8);
+    assert!(grid
+        .neighbors_wrapping((0, 0), Neighborhood::Eight)
+        .all(|pos| pos == (0, 0)));
+
+    // Empty grids have no neighbors, instead of dividing by zero
+    for (width, height) in [(0, 0), (3, 0), (0, 3)] {
+        let grid = Grid::new(width, height, 0u8);
+
+        assert_eq!(
+            grid.neighbors_wrapping((0, 0), Neighborhood::Four).count(),
+            0
+        );
+        assert_eq!(
+            grid.neighbors_wrapping((1, 1), Neighborhood::Eight).count(),
+            0
+        );
+    }
+}
+
+#[test]
+fn rows_and_columns() {
+    let grid = Grid::from_cells(3, vec![1, 2, 3, 4, 5, 6]).unwrap();
+
+    assert_eq!(grid.row(1), &[4, 5, 6]);
+    assert_eq!(
+        grid.rows().collect::<Vec<_>>(),
+        vec![&[1, 2, 3], &[4, 5, 6]]
+    );
+
+    assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+
+    let columns: Vec<Vec<u8>> = grid
+        .columns()
+        .map(|column| column.copied().collect())
+        .collect();
+    assert_eq!(columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+
+    assert_eq!(Grid::<u8>::from_cells(4, vec![1, 2, 3]), None);
+    assert_eq!(
+        Grid::<u8>::from_cells(0, vec![]).unwrap().columns().count(),
+        0
+    );
+}
+
+#[test]
+#[should_panic(expected = "column 3 out of bounds")]
+fn column_out_of_bounds() {
+    let grid = Grid::new(3, 2, 0u8);
+
+    let _ = grid.column(3);
+}
+
+#[test]
+fn display() {
+    let grid = Grid::parse("123\n456\n", "a digit", digit).unwrap();
+
+    assert_eq!(grid.to_string(), "123\n456\n");
+    assert_eq!(
+        grid.display_with(|&d| if d % 2 == 0 { '#' } else { '.' })
+            .to_string(),
+        ".#.\n#.#\n"
+    );
+
+    assert_eq!(Grid::new(0, 0, 0u8).to_string(), "");
+}
@@ -0,0 +1,184 @@
+//! The parser combinators in `util::parse`: what they consume, and where and with which
+//! description they fail
+
+use aoc2021::{
+    error::ParseError,
+    util::parse::{
+        alt, count, digit, integer, label, literal, many, many1, pair, parse_all, preceded,
+        separated, spanned, take, Parser,
+    },
+    Error,
+};
+
+/// The position, the offending text and what was expected instead
+fn parse_error<T: std::fmt::Debug>(result: aoc2021::Result<T>) -> (usize, usize, String, String) {
+    match result {
+        Err(Error::Parse(ParseError {
+            line,
+            column,
+            found,
+            expected,
+            ..
+        })) => (line, column, found, expected),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
+
+fn error(
+    line: usize,
+    column: usize,
+    found: &str,
+    expected: &str,
+) -> (usize, usize, String, String) {
+    (line, column, found.to_owned(), expected.to_owned())
+}
+
+/// `x=<number>, y=<number>`
+fn coordinates<'a>() -> impl Parser<'a, (i32, i32)> {
+    pair(
+        preceded(literal("x="), integer()),
+        preceded(literal(", y="), integer()),
+    )
+}
+
+#[test]
+fn literal_and_integer() {
+    assert_eq!(literal("x=")("x=12"), Ok(("x=", "12")));
+    assert_eq!(literal("x=")("y=12"), Err(("y=12", "x=")));
+
+    assert_eq!(integer::<i32>()("-12,3"), Ok((-12, ",3")));
+    assert_eq!(integer::<i32>()("-"), Err(("-", "a number")));
+    assert_eq!(integer::<u32>()("-1"), Err(("-1", "a number")));
+    assert_eq!(integer::<u8>()("300"), Err(("300", "a number")));
+    assert_eq!(integer::<u8>()(""), Err(("", "a number")));
+}
+
+#[test]
+fn take_counts_characters() {
+    assert_eq!(take(2)("héllo"), Ok(("hé", "llo")));
+    assert_eq!(take(2)("hé"), Ok(("hé", "")));
+    assert_eq!(take(0)(""), Ok(("", "")));
+    assert_eq!(take(3)("hé"), Err(("hé", "more characters")));
+}
+
+#[test]
+fn alt_reports_the_failure_that_got_further() {
+    let command = || {
+        alt(
+            preceded(literal("on "), integer::<i32>()),
+            preceded(literal("off "), integer::<i32>()),
+        )
+    };
+
+    assert_eq!(command()("on 3"), Ok((3, "")));
+    assert_eq!(command()("off 4"), Ok((4, "")));
+
+    // The first alternative got further
+    assert_eq!(command()("on x"), Err(("x", "a number")));
+    // The second one did
+    assert_eq!(command()("off y"), Err(("y", "a number")));
+    // Neither got anywhere, the second one is reported
+    assert_eq!(command()("toggle"), Err(("toggle", "off ")));
+}
+
+#[test]
+fn label_replaces_failures_at_the_start() {
+    let tag = || label(preceded(literal("<"), integer::<u32>()), "a tag");
+
+    assert_eq!(tag()("<7"), Ok((7, "")));
+    assert_eq!(tag()("7"), Err(("7", "a tag")));
+
+    // Past the start, the original failure is more precise and kept
+    assert_eq!(tag()("<x"), Err(("x", "a number")));
+}
+
+#[test]
+fn separated_lists() {
+    let numbers = || separated(integer::<u32>(), literal(","));
+
+    assert_eq!(numbers()("1,2,3 rest"), Ok((vec![1, 2, 3], " rest")));
+    assert_eq!(numbers()("1"), Ok((vec![1], "")));
+
+    // At least one item, and a separator has to be followed by one
+    assert_eq!(numbers()(""), Err(("", "a number")));
+    assert_eq!(numbers()("1,2,"), Err(("", "a number")));
+    assert_eq!(numbers()("1,x"), Err(("x", "a number")));
+
+    // A separator that does not match ends the list
+    assert_eq!(numbers()("1;2"), Ok((vec![1], ";2")));
+}
+
+#[test]
+fn many_and_count() {
+    assert_eq!(many(digit)("12a"), Ok((vec![1, 2], "a")));
+    assert_eq!(many(digit)("a"), Ok((vec![], "a")));
+
+    // A parser that consumes nothing does not loop forever
+    assert_eq!(many(literal(""))("abc"), Ok((vec![], "abc")));
+
+    assert_eq!(many1(digit)("12a"), Ok((vec![1, 2], "a")));
+    assert_eq!(many1(digit)("a"), Err(("a", "a digit")));
+
+    assert_eq!(count(2, digit)("123"), Ok((vec![1, 2], "3")));
+    assert_eq!(count(2, digit)("1x"), Err(("x", "a digit")));
+    assert_eq!(count(2, digit)("1"), Err(("", "a digit")));
+}
+
+#[test]
+fn spanned_returns_a_slice_of_the_input() {
+    let input = "-42 rest";
+    let ((value, span), rem) = spanned(integer::<i32>())(input).unwrap();
+
+    assert_eq!((value, span, rem), (-42, "-42", " rest"));
+
+    // So that errors found later can point at it
+    assert_eq!(
+        parse_error::<()>(Err(Error::parse(input, span, "a positive number"))),
+        error(1, 1, "-42", "a positive number")
+    );
+
+    let input = "a, -42";
+    let ((_, span), _) = spanned(integer::<i32>())(&input[3..]).unwrap();
+    assert_eq!(
+        parse_error::<()>(Err(Error::parse(input, span, "a positive number"))),
+        error(1, 4, "-42", "a positive number")
+    );
+}
+
+#[test]
+fn parse_all_positions() {
+    let input = "first line\nx=1, y=2\nx=1, y=2z\nx=1, y=q\nx=1, y=\néx=1, y=2\n";
+    let lines: Vec<&str> = input.lines().collect();
+
+    assert_eq!(parse_all(input, lines[1], coordinates()).unwrap(), (1, 2));
+
+    // Left over input
+    assert_eq!(
+        parse_error(parse_all(input, lines[2], coordinates())),
+        error(3, 9, "z", "the end of the line")
+    );
+
+    // A failure within the line
+    assert_eq!(
+        parse_error(parse_all(input, lines[3], coordinates())),
+        error(4, 8, "q", "a number")
+    );
+
+    // Something missing at its end
+    assert_eq!(
+        parse_error(parse_all(input, lines[4], coordinates())),
+        error(5, 8, "", "a number")
+    );
+    assert_eq!(
+        parse_all(input, lines[4], coordinates())
+            .unwrap_err()
+            .to_string(),
+        "line 5, column 8: expected a number, found nothing"
+    );
+
+    // Whole characters are reported
+    assert_eq!(
+        parse_error(parse_all(input, lines[5], coordinates())),
+        error(6, 1, "é", "x=")
+    );
+}
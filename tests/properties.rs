@@ -0,0 +1,150 @@
+//! Differential tests between the variants that some days keep side by side. Every variant
+//! gets the same random inputs and has to give the same answers.
+
+use aoc2021::{
+    day1::{self, Day1},
+    day6, generate,
+    property::{self, ensure_eq, shrink_int, shrink_pair, shrink_vec},
+    solvers::{self, Parts},
+    Solution,
+};
+
+const CASES: usize = 500;
+
+#[test]
+fn day1_without_allocating_agrees() {
+    // Small depths, so that equal neighbours and equal window sums are common
+    let depths = |rng: &mut generate::Rng| {
+        let n = rng.below(40);
+        (0..n).map(|_| rng.below(10)).collect::<Vec<u64>>()
+    };
+
+    property::assert(
+        CASES,
+        1,
+        depths,
+        |depths| shrink_vec(depths, |&d| shrink_int(d, 0)),
+        |depths| {
+            let input: String = depths.iter().map(|d| format!("{}\n", d)).collect();
+
+            let nrs = Day1::parse(&input).map_err(|e| e.to_string())?;
+            let part1 = Day1::part1(&nrs).map_err(|e| e.to_string())?;
+            let part2 = Day1::part2(&nrs).map_err(|e| e.to_string())?;
+
+            let (prob1, prob2) =
+                day1::solve_without_allocating(&input).map_err(|e| e.to_string())?;
+
+            ensure_eq(
+                (part1.to_string(), part2.to_string()),
+                (prob1.to_string(), prob2.to_string()),
+            )
+        },
+    );
+}
+
+#[test]
+fn day6_variants_agree() {
+    let school = |rng: &mut generate::Rng| {
+        let n = rng.below(30);
+        let fish: Vec<u8> = (0..n).map(|_| rng.below(9) as u8).collect();
+
+        (fish, rng.below(300) as u16)
+    };
+
+    property::assert(
+        CASES,
+        6,
+        school,
+        |school| {
+            shrink_pair(
+                school,
+                |fish| shrink_vec(fish, |&x| shrink_int(x, 0)),
+                |&days| shrink_int(days, 0),
+            )
+        },
+        |(fish, days)| {
+            let expected = day6::solve_dp_table(fish, *days);
+
+            ensure_eq(day6::solve_memoized_recursion_table(fish, *days), expected)?;
+            ensure_eq(day6::solve_dp_hashmap(fish, *days), expected)?;
+            ensure_eq(day6::solve_memoized_recursion(fish, *days), expected)
+        },
+    );
+}
+
+#[test]
+fn variants_agree_on_generated_inputs() {
+    for seed in 0..20 {
+        let input = generate::generate(1, seed).unwrap().input;
+        let nrs = Day1::parse(&input).unwrap();
+
+        let answers = (
+            Day1::part1(&nrs).unwrap().to_string(),
+            Day1::part2(&nrs).unwrap().to_string(),
+        );
+        let (prob1, prob2) = day1::solve_without_allocating(&input).unwrap();
+
+        assert_eq!(
+            answers,
+            (prob1.to_string(), prob2.to_string()),
+            "seed {}",
+            seed
+        );
+
+        let fish = day6::Day6::parse(&generate::generate(6, seed).unwrap().input).unwrap();
+        let expected = day6::solve_dp_table(&fish, 256);
+
+        assert_eq!(day6::solve_memoized_recursion_table(&fish, 256), expected);
+        assert_eq!(day6::solve_dp_hashmap(&fish, 256), expected);
+        assert_eq!(day6::solve_memoized_recursion(&fish, 256), expected);
+    }
+}
+
+#[test]
+fn registered_variants_agree() {
+    for solver in solvers::SOLVERS.iter().filter(|s| !s.variants.is_empty()) {
+        for seed in 0..5 {
+            let input = generate::generate(solver.day, seed).unwrap().input;
+            let expected = solver.solve(&input, Parts::Both).unwrap();
+
+            for variant in solver.variants {
+                let answers = solver
+                    .variant(variant.name)
+                    .and_then(|v| v.solve(&input, Parts::Both))
+                    .unwrap();
+
+                assert_eq!(answers, expected, "day {} {}", solver.day, variant.name);
+            }
+        }
+    }
+}
+
+#[test]
+fn failures_are_shrunk() {
+    // A deliberately wrong property: no fish school grows beyond 1000 fish
+    let failure = property::check(
+        CASES,
+        1,
+        |rng| {
+            let fish: Vec<u8> = (0..rng.below(10)).map(|_| rng.below(9) as u8).collect();
+            (fish, rng.below(100) as u16)
+        },
+        |school| {
+            shrink_pair(
+                school,
+                |fish| shrink_vec(fish, |&x| shrink_int(x, 0)),
+                |&days| shrink_int(days, 0),
+            )
+        },
+        |(fish, days)| property::ensure(day6::solve_dp_table(fish, *days) <= 1000, "too many fish"),
+    )
+    .unwrap_err();
+
+    // A single fish that is about to spawn needs the fewest days to get there, and one day
+    // fewer is not enough
+    let (fish, days) = &failure.value;
+
+    assert_eq!(fish, &[0]);
+    assert!(day6::solve_dp_table(fish, *days) > 1000);
+    assert!(day6::solve_dp_table(fish, days - 1) <= 1000);
+}